@@ -0,0 +1,220 @@
+//! Generate the builder as source text
+//!
+//! This is parsed back into a [`TokenStream`][proc_macro::TokenStream] by the caller.
+
+use std::fmt::Write as _;
+
+use crate::input::DefaultValue;
+use crate::input::Field;
+use crate::input::Input;
+use crate::input::Kind;
+
+pub(crate) fn builder(input: &Input) -> String {
+    let Input {
+        vis,
+        name,
+        crate_path: krate,
+        fields,
+    } = input;
+    let builder = format!("{name}Builder");
+
+    let mut members = String::new();
+    for field in fields {
+        let ty = &field.ty;
+        let ty = match field.kind {
+            Kind::Flag => "bool".to_owned(),
+            Kind::Multiple => format!("::std::vec::Vec<{ty}>"),
+            Kind::Optional | Kind::Required => format!("::core::option::Option<{ty}>"),
+        };
+        let _ = writeln!(members, "    {}: {ty},", field.ident);
+    }
+
+    let specs = fields.iter().map(|f| spec(krate, f)).collect::<String>();
+
+    let takes_value = fields.iter().any(|f| !f.positional && f.kind != Kind::Flag);
+    let parser = if takes_value { "parser" } else { "_parser" };
+    let arms = fields.iter().map(arm).collect::<String>();
+
+    let finish = fields.iter().map(finish).collect::<String>();
+
+    format!(
+        r#"
+#[doc = "Incrementally parse [`{name}`]"]
+#[derive(Default)]
+{vis} struct {builder} {{
+{members}}}
+
+#[allow(dead_code)]
+impl {builder} {{
+    #[doc = "Arguments accepted by [`{builder}::parse_next`], for generating help"]
+    {vis} const OPTIONS: &'static [{krate}::OptSpec] = &[
+{specs}    ];
+
+    #[doc = "Nothing parsed yet"]
+    {vis} fn new() -> Self {{
+        ::core::default::Default::default()
+    }}
+
+    #[doc = "Check if `arg` is relevant, parsing any values it takes"]
+    #[doc = ""]
+    #[doc = "`arg` is returned if it was not recognized so another parser can be tried."]
+    {vis} fn parse_next<'a>(
+        &mut self,
+        {parser}: &mut {krate}::Parser<'a>,
+        arg: {krate}::Arg<'a>,
+    ) -> ::core::result::Result<::core::option::Option<{krate}::Arg<'a>>, {krate}::LexError<'a>> {{
+        #[allow(unused_imports)]
+        use {krate}::prelude::*;
+
+        match arg {{
+{arms}            _ => return ::core::result::Result::Ok(::core::option::Option::Some(arg)),
+        }}
+        ::core::result::Result::Ok(::core::option::Option::None)
+    }}
+
+    #[doc = "Validate that all required arguments were present"]
+    {vis} fn finish(self) -> ::core::result::Result<{name}, {krate}::LexError<'static>> {{
+        #[allow(unused_imports)]
+        use {krate}::prelude::*;
+
+        ::core::result::Result::Ok({name} {{
+{finish}        }})
+    }}
+}}
+"#
+    )
+}
+
+fn spec(krate: &str, field: &Field) -> String {
+    let mut spec = if field.positional {
+        format!("{krate}::OptSpec::positional({:?})", field.value)
+    } else {
+        let mut spec = format!("{krate}::OptSpec::new()");
+        if let Some(short) = &field.short {
+            let _ = write!(spec, ".short({short:?})");
+        }
+        if let Some(long) = &field.long {
+            let _ = write!(spec, ".long({long:?})");
+        }
+        if field.kind != Kind::Flag {
+            let _ = write!(spec, ".value({:?})", field.value);
+        }
+        spec
+    };
+    if !field.help.is_empty() {
+        let _ = write!(spec, ".help({:?})", field.help);
+    }
+    if !field.one_of.is_empty() {
+        let possible = field
+            .one_of
+            .iter()
+            .map(|p| format!("{krate}::PossibleValue::new({p:?})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = write!(spec, ".possible(&[{possible}])");
+    }
+    if field.kind == Kind::Required && field.default.is_none() {
+        spec.push_str(".required()");
+    }
+    if field.kind == Kind::Multiple {
+        spec.push_str(".multiple()");
+    }
+    format!("        {spec},\n")
+}
+
+fn arm(field: &Field) -> String {
+    let ident = &field.ident;
+    let store = if field.kind == Kind::Multiple {
+        format!("self.{ident}.push(value);")
+    } else {
+        format!("self.{ident} = ::core::option::Option::Some(value);")
+    };
+
+    if field.positional {
+        let guard = if field.kind == Kind::Multiple {
+            String::new()
+        } else {
+            format!(" if self.{ident}.is_none()")
+        };
+        let convert = convert(field);
+        return format!(
+            "            Value(value){guard} => {{
+                let value = value{convert}?;
+                {store}
+            }}
+"
+        );
+    }
+
+    let pattern = field
+        .short
+        .iter()
+        .map(|s| format!("Short({s:?})"))
+        .chain(field.long.iter().map(|l| format!("Long({l:?})")))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    if field.kind == Kind::Flag {
+        return format!(
+            "            {pattern} => {{
+                self.{ident} = true;
+            }}
+"
+        );
+    }
+
+    let value = &field.value;
+    let convert = convert(field);
+    format!(
+        "            {pattern} => {{
+                let value = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(::std::ffi::OsStr::new({value:?})))
+                    {convert}
+                    .within(arg)?;
+                {store}
+            }}
+"
+    )
+}
+
+/// Method chain to turn the raw value into the field's type
+fn convert(field: &Field) -> String {
+    let ty = &field.ty;
+    if !field.one_of.is_empty() {
+        let possible = field
+            .one_of
+            .iter()
+            .map(|p| format!("{p:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            ".one_of(&[{possible}]).and_then(|value| ::std::ffi::OsStr::new(value).parse::<{ty}>())"
+        )
+    } else if field.is_path {
+        ".path().map(::std::path::Path::to_path_buf)".to_owned()
+    } else {
+        format!(".parse::<{ty}>()")
+    }
+}
+
+fn finish(field: &Field) -> String {
+    let ident = &field.ident;
+    let value = match (field.kind, &field.default) {
+        (Kind::Flag | Kind::Optional | Kind::Multiple, _) => format!("self.{ident}"),
+        (Kind::Required, Some(DefaultValue::Trait)) => format!("self.{ident}.unwrap_or_default()"),
+        (Kind::Required, Some(DefaultValue::Expr(expr))) => {
+            format!("self.{ident}.unwrap_or_else(|| {expr})")
+        }
+        (Kind::Required, None) => {
+            let missing = if field.positional {
+                format!("Value(::std::ffi::OsStr::new({:?}))", field.value)
+            } else if let Some(long) = &field.long {
+                format!("Long({long:?})")
+            } else {
+                format!("Short({:?})", field.short.as_deref().unwrap_or_default())
+            };
+            format!("self.{ident}.ok_or_missing({missing})?")
+        }
+    };
+    format!("            {ident}: {value},\n")
+}
@@ -0,0 +1,550 @@
+//! Parse the `struct` being derived, directly from the [`TokenTree`]s
+
+use std::iter::FromIterator;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+pub(crate) struct Input {
+    pub(crate) vis: String,
+    pub(crate) name: String,
+    pub(crate) crate_path: String,
+    pub(crate) fields: Vec<Field>,
+}
+
+pub(crate) struct Field {
+    /// Field as written, including any `r#`
+    pub(crate) ident: String,
+    pub(crate) kind: Kind,
+    /// Type of each value, unwrapping `Option` and `Vec`
+    pub(crate) ty: String,
+    /// Whether the value is a `PathBuf`, skipping UTF-8 validation
+    pub(crate) is_path: bool,
+    pub(crate) short: Option<String>,
+    pub(crate) long: Option<String>,
+    pub(crate) value: String,
+    pub(crate) help: String,
+    pub(crate) positional: bool,
+    pub(crate) default: Option<DefaultValue>,
+    pub(crate) one_of: Vec<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// `bool`
+    Flag,
+    /// `Option<T>`
+    Optional,
+    /// `Vec<T>`
+    Multiple,
+    /// `T`
+    Required,
+}
+
+pub(crate) enum DefaultValue {
+    /// `#[arg(default)]`
+    Trait,
+    /// `#[arg(default = expr)]`
+    Expr(String),
+}
+
+pub(crate) struct Error {
+    span: Span,
+    msg: String,
+}
+
+impl Error {
+    fn new(span: Span, msg: impl Into<String>) -> Self {
+        Self {
+            span,
+            msg: msg.into(),
+        }
+    }
+
+    /// Generates the equivalent of this Rust code as a `TokenStream`:
+    ///
+    /// ```nocompile
+    /// ::core::compile_error!("msg");
+    /// ```
+    pub(crate) fn into_compile_error(self) -> TokenStream {
+        let mut msg = Literal::string(&self.msg);
+        msg.set_span(self.span);
+        let mut args = Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from_iter([TokenTree::Literal(msg)]),
+        );
+        args.set_span(self.span);
+        TokenStream::from_iter([
+            TokenTree::Punct(punct(':', Spacing::Joint, self.span)),
+            TokenTree::Punct(punct(':', Spacing::Alone, self.span)),
+            TokenTree::Ident(Ident::new("core", self.span)),
+            TokenTree::Punct(punct(':', Spacing::Joint, self.span)),
+            TokenTree::Punct(punct(':', Spacing::Alone, self.span)),
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(punct('!', Spacing::Alone, self.span)),
+            TokenTree::Group(args),
+            TokenTree::Punct(punct(';', Spacing::Alone, self.span)),
+        ])
+    }
+}
+
+fn punct(c: char, spacing: Spacing, span: Span) -> Punct {
+    let mut punct = Punct::new(c, spacing);
+    punct.set_span(span);
+    punct
+}
+
+pub(crate) fn parse(item: TokenStream) -> Result<Input, Error> {
+    let tokens = item.into_iter().collect::<Vec<_>>();
+    let mut i = 0;
+
+    let mut crate_path = "::lexarg".to_owned();
+    while let Some(attr) = attribute(&tokens, &mut i) {
+        for (key, value) in arg_attribute(&attr)?.into_iter().flatten() {
+            match key.to_string().as_str() {
+                "crate_path" => {
+                    let value = value
+                        .filter(|v| !v.is_empty())
+                        .ok_or_else(|| Error::new(key.span(), "expected `crate_path = <path>`"))?;
+                    crate_path = to_string(&value);
+                }
+                _ => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("unknown struct attribute `{key}`"),
+                    ))
+                }
+            }
+        }
+    }
+
+    let vis = visibility(&tokens, &mut i);
+
+    match tokens.get(i) {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {}
+        Some(token) => {
+            return Err(Error::new(
+                token.span(),
+                "`Parse` can only be derived for structs",
+            ))
+        }
+        None => return Err(Error::new(Span::call_site(), "expected a struct")),
+    }
+    i += 1;
+
+    let Some(TokenTree::Ident(name)) = tokens.get(i) else {
+        return Err(Error::new(Span::call_site(), "expected a struct name"));
+    };
+    i += 1;
+
+    let body = match tokens.get(i) {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err(Error::new(
+                punct.span(),
+                "`Parse` does not support generic structs",
+            ))
+        }
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                "`Parse` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut fields = Vec::new();
+    for field in split_commas(&body.stream().into_iter().collect::<Vec<_>>()) {
+        if field.is_empty() {
+            continue;
+        }
+        fields.push(parse_field(&field)?);
+    }
+
+    let mut positional_vec: Option<&Field> = None;
+    for field in fields.iter().filter(|f| f.positional) {
+        if let Some(vec) = positional_vec {
+            return Err(Error::new(
+                name.span(),
+                format!(
+                    "positional `{}` must come before `{}` which takes all remaining values",
+                    field.ident, vec.ident
+                ),
+            ));
+        }
+        if field.kind == Kind::Multiple {
+            positional_vec = Some(field);
+        }
+    }
+
+    Ok(Input {
+        vis,
+        name: name.to_string(),
+        crate_path,
+        fields,
+    })
+}
+
+fn parse_field(tokens: &[TokenTree]) -> Result<Field, Error> {
+    let mut i = 0;
+
+    let mut docs = Vec::new();
+    let mut args = Vec::new();
+    while let Some(attr) = attribute(tokens, &mut i) {
+        if let Some(doc) = doc_attribute(&attr) {
+            docs.push(doc);
+        } else if let Some(arg) = arg_attribute(&attr)? {
+            args.extend(arg);
+        }
+    }
+
+    let _vis = visibility(tokens, &mut i);
+
+    let Some(TokenTree::Ident(ident)) = tokens.get(i) else {
+        return Err(Error::new(
+            tokens[0].span(),
+            "`Parse` can only be derived for structs with named fields",
+        ));
+    };
+    i += 1;
+    match tokens.get(i) {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+        _ => return Err(Error::new(ident.span(), "expected `:` after field name")),
+    }
+    i += 1;
+    let ty = &tokens[i..];
+    if ty.is_empty() {
+        return Err(Error::new(ident.span(), "expected a type after `:`"));
+    }
+
+    let (kind, ty) = classify(ty);
+    if kind != Kind::Required && matches!(classify(ty).0, Kind::Optional | Kind::Multiple) {
+        return Err(Error::new(
+            ident.span(),
+            "nested `Option` and `Vec` are not supported, like `Option<Vec<T>>`; use `Vec<T>`",
+        ));
+    }
+    let name = ident.to_string();
+    let name = name.strip_prefix("r#").unwrap_or(&name).to_owned();
+    let is_path =
+        matches!(ty.last(), Some(TokenTree::Ident(last)) if last.to_string() == "PathBuf");
+
+    let mut field = Field {
+        ident: ident.to_string(),
+        kind,
+        ty: to_string(ty),
+        is_path,
+        short: None,
+        long: None,
+        value: name.to_uppercase(),
+        help: help(&docs),
+        positional: false,
+        default: None,
+        one_of: Vec::new(),
+    };
+    for (key, value) in args {
+        match key.to_string().as_str() {
+            "short" => {
+                let short = string_value(&key, value.as_deref())?;
+                if short.chars().count() != 1 {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("`short` must be a single character, found `{short}`"),
+                    ));
+                }
+                field.short = Some(short);
+            }
+            "long" => field.long = Some(string_value(&key, value.as_deref())?),
+            "value" => field.value = string_value(&key, value.as_deref())?,
+            "help" => field.help = string_value(&key, value.as_deref())?,
+            "positional" => {
+                no_value(&key, value.as_deref())?;
+                field.positional = true;
+            }
+            "default" => {
+                let default = match value {
+                    Some(value) if value.is_empty() => {
+                        return Err(Error::new(key.span(), "expected `default = <expr>`"));
+                    }
+                    Some(value) => DefaultValue::Expr(to_string(&value)),
+                    None => DefaultValue::Trait,
+                };
+                if kind != Kind::Required {
+                    return Err(Error::new(
+                        key.span(),
+                        "`default` is not supported for `bool`, `Option`, or `Vec`",
+                    ));
+                }
+                field.default = Some(default);
+            }
+            "one_of" => {
+                field.one_of = string_list_value(&key, value.as_deref())?;
+            }
+            _ => {
+                return Err(Error::new(
+                    key.span(),
+                    format!("unknown field attribute `{key}`"),
+                ))
+            }
+        }
+    }
+    if kind == Kind::Flag && field.positional {
+        return Err(Error::new(ident.span(), "`bool` cannot be `positional`"));
+    }
+    if kind == Kind::Flag && !field.one_of.is_empty() {
+        return Err(Error::new(ident.span(), "`bool` cannot have `one_of`"));
+    }
+    if !field.positional && field.long.is_none() {
+        field.long = Some(name.replace('_', "-"));
+    }
+
+    Ok(field)
+}
+
+/// Determine the [`Kind`] from the type, returning the type of each value
+fn classify(ty: &[TokenTree]) -> (Kind, &[TokenTree]) {
+    let open = ty
+        .iter()
+        .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '<'));
+    let close = ty.len() - 1;
+    match open {
+        Some(open) if 0 < open && is_punct(&ty[close], '>') => {
+            let kind = match ty[open - 1].to_string().as_str() {
+                "Option" => Kind::Optional,
+                "Vec" => Kind::Multiple,
+                _ => return (Kind::Required, ty),
+            };
+            (kind, &ty[open + 1..close])
+        }
+        None if to_string(ty) == "bool" => (Kind::Flag, ty),
+        _ => (Kind::Required, ty),
+    }
+}
+
+/// Join doc comment paragraphs into one line each
+fn help(docs: &[String]) -> String {
+    let mut paragraphs = vec![Vec::new()];
+    for line in docs.iter().flat_map(|d| d.split('\n')) {
+        let line = line.trim();
+        if line.is_empty() {
+            if paragraphs.last().is_some_and(|p| !p.is_empty()) {
+                paragraphs.push(Vec::new());
+            }
+        } else {
+            paragraphs
+                .last_mut()
+                .expect("always at least one")
+                .push(line);
+        }
+    }
+    paragraphs
+        .into_iter()
+        .filter(|p| !p.is_empty())
+        .map(|p| p.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse `#[...]` at `i`
+fn attribute(tokens: &[TokenTree], i: &mut usize) -> Option<Group> {
+    match (tokens.get(*i), tokens.get(*i + 1)) {
+        (Some(TokenTree::Punct(punct)), Some(TokenTree::Group(group)))
+            if punct.as_char() == '#' && group.delimiter() == Delimiter::Bracket =>
+        {
+            *i += 2;
+            Some(group.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Parse `pub`, `pub(crate)`, etc at `i`
+fn visibility(tokens: &[TokenTree], i: &mut usize) -> String {
+    match tokens.get(*i) {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+            *i += 1;
+            match tokens.get(*i) {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    *i += 1;
+                    format!("pub{group}")
+                }
+                _ => "pub".to_owned(),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// Contents of `#[doc = "..."]`
+fn doc_attribute(attr: &Group) -> Option<String> {
+    let tokens = attr.stream().into_iter().collect::<Vec<_>>();
+    match tokens.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Punct(punct), TokenTree::Literal(lit)]
+            if ident.to_string() == "doc" && punct.as_char() == '=' =>
+        {
+            unquote(lit)
+        }
+        _ => None,
+    }
+}
+
+/// `key` or `key = value`
+type ArgPair = (Ident, Option<Vec<TokenTree>>);
+
+/// `key` or `key = value` pairs of `#[arg(...)]`
+fn arg_attribute(attr: &Group) -> Result<Option<Vec<ArgPair>>, Error> {
+    let tokens = attr.stream().into_iter().collect::<Vec<_>>();
+    let args = match tokens.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Group(args)]
+            if ident.to_string() == "arg" && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            args
+        }
+        [TokenTree::Ident(ident), ..] if ident.to_string() == "arg" => {
+            return Err(Error::new(ident.span(), "expected `#[arg(...)]`"));
+        }
+        _ => return Ok(None),
+    };
+
+    let mut pairs = Vec::new();
+    for arg in split_commas(&args.stream().into_iter().collect::<Vec<_>>()) {
+        match arg.as_slice() {
+            [] => {}
+            [TokenTree::Ident(key)] => pairs.push((key.clone(), None)),
+            [TokenTree::Ident(key), eq, value @ ..] if is_punct(eq, '=') => {
+                pairs.push((key.clone(), Some(value.to_vec())));
+            }
+            [token, ..] => return Err(Error::new(token.span(), "expected `key` or `key = value`")),
+        }
+    }
+    Ok(Some(pairs))
+}
+
+fn no_value(key: &Ident, value: Option<&[TokenTree]>) -> Result<(), Error> {
+    match value {
+        Some(_) => Err(Error::new(
+            key.span(),
+            format!("`{key}` does not take a value"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn string_value(key: &Ident, value: Option<&[TokenTree]>) -> Result<String, Error> {
+    match value {
+        Some([TokenTree::Literal(lit)]) => unquote(lit).filter(|s| !s.is_empty()),
+        _ => None,
+    }
+    .ok_or_else(|| Error::new(key.span(), format!("expected `{key} = \"...\"`")))
+}
+
+fn string_list_value(key: &Ident, value: Option<&[TokenTree]>) -> Result<Vec<String>, Error> {
+    let error = || Error::new(key.span(), format!("expected `{key} = [\"...\", ...]`"));
+    let Some([TokenTree::Group(group)]) = value else {
+        return Err(error());
+    };
+    if group.delimiter() != Delimiter::Bracket {
+        return Err(error());
+    }
+    let mut list = Vec::new();
+    for item in split_commas(&group.stream().into_iter().collect::<Vec<_>>()) {
+        match item.as_slice() {
+            [] => {}
+            [TokenTree::Literal(lit)] => list.push(unquote(lit).ok_or_else(error)?),
+            _ => return Err(error()),
+        }
+    }
+    if list.is_empty() {
+        return Err(error());
+    }
+    Ok(list)
+}
+
+/// Split on `,`, ignoring those within `<...>`
+///
+/// Other delimiters are already grouped by the compiler.
+fn split_commas(tokens: &[TokenTree]) -> Vec<Vec<TokenTree>> {
+    let mut items = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut prev_joint_dash = false;
+    for token in tokens {
+        let mut joint_dash = false;
+        if let TokenTree::Punct(punct) = token {
+            match punct.as_char() {
+                ',' if depth == 0 => {
+                    items.push(Vec::new());
+                    continue;
+                }
+                '<' => depth += 1,
+                // Skip `->`
+                '>' if !prev_joint_dash => depth = depth.saturating_sub(1),
+                '-' => joint_dash = punct.spacing() == Spacing::Joint,
+                _ => {}
+            }
+        }
+        prev_joint_dash = joint_dash;
+        items
+            .last_mut()
+            .expect("always at least one")
+            .push(token.clone());
+    }
+    items
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(p) if p.as_char() == c)
+}
+
+fn to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Value of a string literal, if it is one
+fn unquote(lit: &Literal) -> Option<String> {
+    let lit = lit.to_string();
+    if let Some(raw) = lit.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..raw.len() - hashes];
+        return raw
+            .strip_prefix('"')
+            .and_then(|r| r.strip_suffix('"'))
+            .map(|r| r.to_owned());
+    }
+
+    let lit = lit.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = lit.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let hex = chars.by_ref().take(2).collect::<String>();
+                value.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+            }
+            'u' => {
+                let hex = chars
+                    .by_ref()
+                    .skip(1)
+                    .take_while(|c| *c != '}')
+                    .collect::<String>();
+                value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            '\n' => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(value)
+}
@@ -0,0 +1,116 @@
+//! Derive a [`lexarg`](https://docs.rs/lexarg) plugin parser
+//!
+//! See `lexarg::Parse`, available with `lexarg`'s `derive` feature.
+
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![warn(clippy::print_stderr)]
+#![warn(clippy::print_stdout)]
+
+mod generate;
+mod input;
+
+use proc_macro::TokenStream;
+
+/// Generate a plugin parser for a struct
+///
+/// For `struct Args`, this generates an `ArgsBuilder` in the same shape as a hand-written plugin:
+/// - `ArgsBuilder::new()`
+/// - `ArgsBuilder::parse_next(&mut self, parser, arg)`, returning `arg` if it was not recognized
+///   so other plugins can try it
+/// - `ArgsBuilder::finish(self)`, reporting required arguments that are missing
+/// - `ArgsBuilder::OPTIONS`, the `OptSpec`s for generating help
+///
+/// Each field is an argument, depending on its type:
+/// - `bool`: a flag
+/// - `Option<T>`: an optional flag that takes a value
+/// - `Vec<T>`: a flag that takes a value and may be repeated
+/// - `T`: a required flag that takes a value, unless it has a `default`
+///
+/// These don't nest, like `Option<Vec<T>>`, as an empty `Vec` already means the flag was missing.
+///
+/// Values are parsed with [`FromStr`][std::str::FromStr], except `PathBuf` which is taken as-is.
+///
+/// The doc comment of a field is its help.
+///
+/// Field attributes:
+/// - `#[arg(short = "n")]`: a single character
+/// - `#[arg(long = "name")]`: defaults to the field name, with `_` replaced by `-`
+/// - `#[arg(value = "NUM")]`: placeholder for the value, defaults to the field name in upper case
+/// - `#[arg(positional)]`: a value, rather than a flag, filled in field order.  A `Vec` takes
+///   all remaining values.
+/// - `#[arg(default)]`, `#[arg(default = expr)]`: use [`Default::default`] or `expr` when missing
+/// - `#[arg(one_of = ["a", "b"])]`: restrict the value to a closed set before parsing it, like for
+///   an `enum`
+/// - `#[arg(help = "...")]`: overrides the doc comment
+///
+/// Struct attributes:
+/// - `#[arg(crate_path = path)]`: path to `lexarg`, when re-exported
+///
+/// Generics are not supported.
+///
+/// ```rust
+/// #[derive(lexarg::Parse)]
+/// struct Args {
+///     /// What to greet
+///     #[arg(positional)]
+///     thing: String,
+///     /// Number of times to greet
+///     #[arg(short = "n", value = "NUM", default = 1)]
+///     number: u32,
+///     /// Use all caps
+///     shout: bool,
+/// }
+///
+/// let raw = ["hello", "-n", "2", "world"];
+/// let mut parser = lexarg::Parser::new(&raw);
+/// let _bin = parser.next_raw();
+/// let mut args = ArgsBuilder::new();
+/// while let Some(arg) = parser.next_arg() {
+///     if let Some(arg) = args.parse_next(&mut parser, arg).unwrap() {
+///         panic!("unexpected argument: {arg:?}");
+///     }
+/// }
+/// let args = args.finish().unwrap();
+/// assert_eq!(args.thing, "world");
+/// assert_eq!(args.number, 2);
+/// assert!(!args.shout);
+///
+/// let help = lexarg::Help::new("hello").specs(ArgsBuilder::OPTIONS).to_string();
+/// assert_eq!(help, "\
+/// Usage: hello [OPTIONS] <THING>
+///
+/// Arguments:
+///       <THING>         What to greet
+///
+/// Options:
+///   -n, --number NUM    Number of times to greet
+///       --shout         Use all caps
+/// ");
+/// ```
+#[proc_macro_derive(Parse, attributes(arg))]
+pub fn parse(item: TokenStream) -> TokenStream {
+    match input::parse(item) {
+        Ok(input) => generate::builder(&input)
+            .parse()
+            .expect("generated code is valid Rust"),
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+/// ```compile_fail
+/// #[derive(lexarg::Parse)]
+/// struct Args {
+///     #[arg(short = "ab")]
+///     all: bool,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(lexarg::Parse)]
+/// struct Args {
+///     skip: Option<Vec<String>>,
+/// }
+/// ```
+#[cfg(doctest)]
+#[allow(dead_code)]
+struct CompileFailDoctests;
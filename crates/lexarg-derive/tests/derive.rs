@@ -0,0 +1,214 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+impl std::str::FromStr for Color {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Ok(Self::Auto),
+        }
+    }
+}
+
+/// Mirrors some of `libtest_lexarg::TestOptsBuilder`
+#[derive(Debug, lexarg::Parse)]
+struct Opts {
+    /// List all tests and benchmarks
+    list: bool,
+    /// Number of threads used for running tests in parallel
+    #[arg(value = "NUM")]
+    test_threads: Option<usize>,
+    /// Skip tests whose names contain FILTER
+    ///
+    /// This can be used multiple times.
+    #[arg(value = "FILTER")]
+    skip: Vec<String>,
+    /// Configure coloring of output
+    #[arg(value = "WHEN", one_of = ["auto", "always", "never"], default = Color::Auto)]
+    color: Color,
+    #[arg(short = "q", long = "quiet")]
+    quiet: bool,
+    /// Write the report here
+    logfile: std::path::PathBuf,
+    /// Run only tests containing FILTER
+    #[arg(positional, value = "FILTER")]
+    filters: Vec<String>,
+}
+
+#[derive(Debug, lexarg::Parse)]
+struct Positionals {
+    #[arg(positional)]
+    first: String,
+    #[arg(positional, default)]
+    second: u32,
+}
+
+fn parse<'a>(
+    raw: &'a dyn lexarg::RawArgs,
+    builder: &mut OptsBuilder,
+) -> Result<Vec<lexarg::Arg<'a>>, lexarg::LexError<'a>> {
+    let mut parser = lexarg::Parser::new(raw);
+    let _bin = parser.next_raw();
+    let mut unrecognized = Vec::new();
+    while let Some(arg) = parser.next_arg() {
+        unrecognized.extend(builder.parse_next(&mut parser, arg)?);
+    }
+    Ok(unrecognized)
+}
+
+#[test]
+fn parse_all() {
+    let raw = [
+        "bin",
+        "--list",
+        "--test-threads=2",
+        "--skip",
+        "one",
+        "--skip=two",
+        "--color",
+        "never",
+        "-q",
+        "--logfile",
+        "out.log",
+        "foo",
+        "bar",
+    ];
+    let mut builder = OptsBuilder::new();
+    let unrecognized = parse(&raw, &mut builder).unwrap();
+    assert_eq!(unrecognized, []);
+    let opts = builder.finish().unwrap();
+    assert!(opts.list);
+    assert_eq!(opts.test_threads, Some(2));
+    assert_eq!(opts.skip, ["one", "two"]);
+    assert_eq!(opts.color, Color::Never);
+    assert!(opts.quiet);
+    assert_eq!(opts.logfile, std::path::Path::new("out.log"));
+    assert_eq!(opts.filters, ["foo", "bar"]);
+}
+
+#[test]
+fn defaults() {
+    let raw = ["bin", "--logfile", "out.log"];
+    let mut builder = OptsBuilder::new();
+    parse(&raw, &mut builder).unwrap();
+    let opts = builder.finish().unwrap();
+    assert!(!opts.list);
+    assert_eq!(opts.test_threads, None);
+    assert!(opts.skip.is_empty());
+    assert_eq!(opts.color, Color::Auto);
+    assert!(!opts.quiet);
+    assert!(opts.filters.is_empty());
+}
+
+#[test]
+fn unrecognized_is_returned() {
+    let raw = ["bin", "--bench", "-x", "--logfile", "out.log"];
+    let mut builder = OptsBuilder::new();
+    let unrecognized = parse(&raw, &mut builder).unwrap();
+    assert_eq!(
+        unrecognized,
+        [lexarg::Arg::Long("bench"), lexarg::Arg::Short("x")]
+    );
+}
+
+#[test]
+fn missing_required() {
+    let raw = ["bin"];
+    let mut builder = OptsBuilder::new();
+    parse(&raw, &mut builder).unwrap();
+    let err = builder.finish().unwrap_err();
+    assert_eq!(err.to_string(), "missing required `--logfile`");
+}
+
+#[test]
+fn missing_value() {
+    let raw = ["bin", "--test-threads"];
+    let mut builder = OptsBuilder::new();
+    let err = parse(&raw, &mut builder).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "missing required `NUM` when parsing `--test-threads`"
+    );
+}
+
+#[test]
+fn invalid_value() {
+    let raw = ["bin", "--test-threads", "many"];
+    let mut builder = OptsBuilder::new();
+    let err = parse(&raw, &mut builder).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid digit found in string, found `many` when parsing `--test-threads`"
+    );
+}
+
+#[test]
+fn invalid_one_of() {
+    let raw = ["bin", "--color", "nevr"];
+    let mut builder = OptsBuilder::new();
+    let err = parse(&raw, &mut builder).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected one of `auto`, `always`, `never`, found `nevr` when parsing `--color`
+
+tip: a similar value exists: 'never'"
+    );
+}
+
+#[test]
+fn positionals_in_order() {
+    let raw = ["bin", "one", "2", "three"];
+    let mut parser = lexarg::Parser::new(&raw);
+    let _bin = parser.next_raw();
+    let mut builder = PositionalsBuilder::new();
+    let mut unrecognized = Vec::new();
+    while let Some(arg) = parser.next_arg() {
+        unrecognized.extend(builder.parse_next(&mut parser, arg).unwrap());
+    }
+    assert_eq!(
+        unrecognized,
+        [lexarg::Arg::Value(std::ffi::OsStr::new("three"))]
+    );
+    let positionals = builder.finish().unwrap();
+    assert_eq!(positionals.first, "one");
+    assert_eq!(positionals.second, 2);
+
+    let err = PositionalsBuilder::new().finish().unwrap_err();
+    assert_eq!(err.to_string(), "missing required `FIRST`");
+}
+
+#[test]
+fn help() {
+    let help = lexarg::Help::new("bin")
+        .specs(OptsBuilder::OPTIONS)
+        .to_string();
+    assert_eq!(
+        help,
+        "\
+Usage: bin [OPTIONS] [FILTER]...
+
+Arguments:
+      [FILTER]...     Run only tests containing FILTER
+
+Options:
+      --list          List all tests and benchmarks
+      --test-threads NUM
+                      Number of threads used for running tests in parallel
+      --skip FILTER   Skip tests whose names contain FILTER
+                      This can be used multiple times.
+                      (this flag can be used multiple times)
+      --color <auto|always|never>
+                      Configure coloring of output
+  -q, --quiet
+      --logfile LOGFILE
+                      Write the report here
+"
+    );
+}
@@ -21,6 +21,23 @@ pub struct LexError<'a> {
     msg: String,
     within: Option<lexarg_parser::Arg<'a>>,
     unexpected: Option<lexarg_parser::Arg<'a>>,
+    // Boxed as this is rarely needed and errors should stay small
+    location: Option<Box<lexarg_parser::Location>>,
+    suggestion: Option<Suggestion>,
+}
+
+#[derive(Debug)]
+struct Suggestion {
+    /// What is being suggested, like "argument"
+    kind: &'static str,
+    value: String,
+}
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { kind, value } = self;
+        write!(formatter, "tip: a similar {kind} exists: '{value}'")
+    }
 }
 
 impl<'a> LexError<'a> {
@@ -34,6 +51,8 @@ impl<'a> LexError<'a> {
             msg: message.to_string(),
             within: None,
             unexpected: None,
+            location: None,
+            suggestion: None,
         }
     }
 
@@ -50,6 +69,115 @@ impl<'a> LexError<'a> {
         self.unexpected = Some(unexpected);
         self
     }
+
+    /// Where the error occurred, see [`Parser::location`][lexarg_parser::Parser::location]
+    ///
+    /// This is needed when the argument the error occurred [within][LexError::within] is not
+    /// borrowed from the raw arguments, like after resolving an abbreviation.
+    #[cold]
+    pub fn located(mut self, location: lexarg_parser::Location) -> Self {
+        self.location = Some(Box::new(location));
+        self
+    }
+
+    /// A similar, valid argument the user may have intended
+    #[cold]
+    pub fn suggest(mut self, suggestion: impl std::fmt::Display) -> Self {
+        self.suggestion = Some(Suggestion {
+            kind: "argument",
+            value: suggestion.to_string(),
+        });
+        self
+    }
+
+    /// A similar, valid value the user may have intended, like for a flag's value
+    #[cold]
+    pub fn suggest_value(mut self, suggestion: impl std::fmt::Display) -> Self {
+        self.suggestion = Some(Suggestion {
+            kind: "value",
+            value: suggestion.to_string(),
+        });
+        self
+    }
+
+    /// See [`LexError::suggest`] and [`LexError::suggest_value`]
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_ref().map(|s| s.value.as_str())
+    }
+
+    /// See [`LexError::within`]
+    pub fn within_arg(&self) -> Option<lexarg_parser::Arg<'a>> {
+        self.within
+    }
+
+    /// See [`LexError::unexpected`]
+    pub fn unexpected_arg(&self) -> Option<lexarg_parser::Arg<'a>> {
+        self.unexpected
+    }
+
+    /// Find the offending argument in `raw`
+    ///
+    /// This is the [unexpected][LexError::unexpected] argument, if present in `raw`,
+    /// otherwise where the error was [located][LexError::located] or the argument it occurred
+    /// [within][LexError::within].
+    pub fn locate(&self, raw: &dyn lexarg_parser::RawArgs) -> Option<lexarg_parser::Location> {
+        self.unexpected
+            .and_then(|arg| arg.locate(raw))
+            .or_else(|| self.location.as_deref().cloned())
+            .or_else(|| self.within.and_then(|arg| arg.locate(raw)))
+    }
+
+    /// Render the error with the command line, pointing at the offending argument
+    ///
+    /// ```rust
+    /// # use lexarg_parser::Arg::*;
+    /// let raw = ["/path/to/hello", "-n", "ten", "world"];
+    /// let mut parser = lexarg_parser::Parser::new(&raw);
+    /// let _bin = parser.next_raw();
+    /// let arg = parser.next_arg().unwrap();
+    /// let value = parser.next_flag_value().unwrap();
+    /// let error = lexarg_error::LexError::msg("invalid digit found in string")
+    ///     .unexpected(Value(value))
+    ///     .within(arg);
+    /// let rendered = error
+    ///     .render(&raw)
+    ///     .usage("Usage: hello [OPTIONS] <THING>")
+    ///     .help_flag("--help")
+    ///     .to_string();
+    /// assert_eq!(rendered, "\
+    /// error: invalid digit found in string, found `ten` when parsing `-n`
+    ///
+    ///   hello -n ten world
+    ///            ^^^
+    ///
+    /// Usage: hello [OPTIONS] <THING>
+    ///
+    /// For more information, try '--help'.
+    /// ");
+    /// ```
+    pub fn render<'e>(&'e self, raw: &'e dyn lexarg_parser::RawArgs) -> Rendered<'e, 'a> {
+        Rendered {
+            error: self,
+            raw,
+            usage: None,
+            help_flag: None,
+        }
+    }
+
+    fn fmt_message(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}", self.msg)?;
+        if let Some(unexpected) = &self.unexpected {
+            write!(formatter, ", found `")?;
+            fmt_arg(unexpected, formatter)?;
+            write!(formatter, "`")?;
+        }
+        if let Some(within) = &self.within {
+            write!(formatter, " when parsing `")?;
+            fmt_arg(within, formatter)?;
+            write!(formatter, "`")?;
+        }
+        Ok(())
+    }
 }
 
 impl<E> From<E> for LexError<'_>
@@ -64,35 +192,128 @@ where
 
 impl std::fmt::Display for LexError<'_> {
     fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.msg.fmt(formatter)?;
-        if let Some(unexpected) = &self.unexpected {
-            write!(formatter, ", found `")?;
-            match unexpected {
-                lexarg_parser::Arg::Short(short) => write!(formatter, "-{short}")?,
-                lexarg_parser::Arg::Long(long) => write!(formatter, "--{long}")?,
-                lexarg_parser::Arg::Escape(value) => write!(formatter, "{value}")?,
-                lexarg_parser::Arg::Value(value) | lexarg_parser::Arg::Unexpected(value) => {
-                    write!(formatter, "{}", value.to_string_lossy())?;
-                }
-            }
-            write!(formatter, "`")?;
+        self.fmt_message(formatter)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(formatter, "\n\n{suggestion}")?;
         }
-        if let Some(within) = &self.within {
-            write!(formatter, " when parsing `")?;
-            match within {
-                lexarg_parser::Arg::Short(short) => write!(formatter, "-{short}")?,
-                lexarg_parser::Arg::Long(long) => write!(formatter, "--{long}")?,
-                lexarg_parser::Arg::Escape(value) => write!(formatter, "{value}")?,
-                lexarg_parser::Arg::Value(value) | lexarg_parser::Arg::Unexpected(value) => {
-                    write!(formatter, "{}", value.to_string_lossy())?;
+        Ok(())
+    }
+}
+
+fn fmt_arg(
+    arg: &lexarg_parser::Arg<'_>,
+    formatter: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    match arg {
+        lexarg_parser::Arg::Short(short) => write!(formatter, "-{short}"),
+        lexarg_parser::Arg::Long(long) => write!(formatter, "--{long}"),
+        lexarg_parser::Arg::Escape(value) => write!(formatter, "{value}"),
+        lexarg_parser::Arg::Value(value) | lexarg_parser::Arg::Unexpected(value) => {
+            write!(formatter, "{}", value.to_string_lossy())
+        }
+    }
+}
+
+/// A [`LexError`] rendered with the command line, see [`LexError::render`]
+#[derive(Debug)]
+pub struct Rendered<'e, 'a> {
+    error: &'e LexError<'a>,
+    raw: &'e dyn lexarg_parser::RawArgs,
+    usage: Option<&'e str>,
+    help_flag: Option<&'e str>,
+}
+
+impl<'e> Rendered<'e, '_> {
+    /// Usage line to remind the user of the expected arguments
+    pub fn usage(mut self, usage: &'e str) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Flag to point the user to for more information, like `--help`
+    pub fn help_flag(mut self, help_flag: &'e str) -> Self {
+        self.help_flag = Some(help_flag);
+        self
+    }
+}
+
+/// Arguments to show on each side of the offending argument
+const CONTEXT: usize = 3;
+
+impl std::fmt::Display for Rendered<'_, '_> {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "error: ")?;
+        self.error.fmt_message(formatter)?;
+        writeln!(formatter)?;
+
+        if let Some(location) = self.error.locate(self.raw) {
+            let first = location.index().saturating_sub(CONTEXT);
+            let last = (location.index() + CONTEXT).min(self.raw.len() - 1);
+            let mut line = String::from("  ");
+            let mut marker = String::new();
+            if 0 < first {
+                line.push_str(&display_arg(self.raw, 0));
+                line.push_str(" ...");
+            }
+            for index in first..=last {
+                if index != 0 {
+                    line.push(' ');
                 }
+                let arg = display_arg(self.raw, index);
+                if index == location.index() {
+                    let raw = self.raw.get(index).expect("`index` is in range");
+                    let quoted = usize::from(arg.len() != raw.len() && arg.starts_with('\''));
+                    let bytes = raw.as_encoded_bytes();
+                    let prefix = String::from_utf8_lossy(&bytes[..location.range().start]);
+                    let span = String::from_utf8_lossy(&bytes[location.range()]);
+                    let offset = line.chars().count() + quoted + prefix.chars().count();
+                    let width = span.chars().count().max(1);
+                    marker = format!("{:offset$}{:^<width$}", "", "");
+                }
+                line.push_str(&arg);
             }
-            write!(formatter, "`")?;
+            if last + 1 < self.raw.len() {
+                line.push_str(" ...");
+            }
+            writeln!(formatter)?;
+            writeln!(formatter, "{line}")?;
+            writeln!(formatter, "{marker}")?;
+        }
+
+        if let Some(suggestion) = &self.error.suggestion {
+            writeln!(formatter)?;
+            writeln!(formatter, "{suggestion}")?;
+        }
+        if let Some(usage) = self.usage {
+            writeln!(formatter)?;
+            writeln!(formatter, "{usage}")?;
+        }
+        if let Some(help_flag) = self.help_flag {
+            writeln!(formatter)?;
+            writeln!(formatter, "For more information, try '{help_flag}'.")?;
         }
         Ok(())
     }
 }
 
+/// Show an argument like a user would type it
+///
+/// The binary name is abbreviated to its file name.
+fn display_arg(raw: &dyn lexarg_parser::RawArgs, index: usize) -> String {
+    let arg = raw.get(index).expect("`index` is in range");
+    let arg = if index == 0 {
+        std::path::Path::new(arg).file_name().unwrap_or(arg)
+    } else {
+        arg
+    };
+    let arg = arg.to_string_lossy();
+    if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '\'') {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    } else {
+        arg.into_owned()
+    }
+}
+
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
 pub struct ReadmeDoctests;
@@ -29,6 +29,7 @@ pub struct Parser<'a> {
     current: usize,
     state: Option<State<'a>>,
     was_attached: bool,
+    last: Option<Span>,
 }
 
 impl<'a> Parser<'a> {
@@ -49,6 +50,7 @@ impl<'a> Parser<'a> {
             current: 0,
             state: None,
             was_attached: false,
+            last: None,
         }
     }
 
@@ -68,6 +70,7 @@ impl<'a> Parser<'a> {
         match self.state {
             Some(State::PendingValue(attached)) => {
                 // Last time we got `--long=value`, and `value` hasn't been used.
+                self.mark_suffix(attached);
                 self.state = None;
                 self.current += 1;
                 Some(Arg::Unexpected(attached))
@@ -76,6 +79,7 @@ impl<'a> Parser<'a> {
                 // We're somewhere inside a `-abc` chain. Because we're in `.next_arg()`, not `.next_flag_value()`, we
                 // can assume that the next character is another option.
                 if let Some(next_index) = ceil_char_boundary(valid, index) {
+                    self.mark(index..next_index);
                     if next_index < valid.len() {
                         self.state = Some(State::PendingShorts(valid, invalid, next_index));
                     } else if !invalid.is_empty() {
@@ -97,10 +101,12 @@ impl<'a> Parser<'a> {
                             .raw
                             .get(self.current)
                             .expect("`current` is valid if state is `Shorts`");
+                        self.mark(0..arg.len());
                         self.state = None;
                         self.current += 1;
                         Some(Arg::Unexpected(arg))
                     } else {
+                        self.mark_suffix(invalid);
                         self.state = None;
                         self.current += 1;
                         Some(Arg::Unexpected(invalid))
@@ -113,6 +119,7 @@ impl<'a> Parser<'a> {
             }
             None => {
                 let arg = self.raw.get(self.current)?;
+                self.mark(0..arg.len());
                 if arg == "--" {
                     self.state = Some(State::Escaped);
                     self.current += 1;
@@ -131,6 +138,8 @@ impl<'a> Parser<'a> {
                         self.current += 1;
                         Some(Arg::Unexpected(arg))
                     } else if let Ok(name) = name.try_str() {
+                        // Include the `--`
+                        self.mark(0..2 + name.len());
                         if let Some(value) = value {
                             self.state = Some(State::PendingValue(value));
                         } else {
@@ -186,6 +195,7 @@ impl<'a> Parser<'a> {
     pub fn next_attached_value(&mut self) -> Option<&'a OsStr> {
         match self.state? {
             State::PendingValue(attached) => {
+                self.mark_suffix(attached);
                 self.state = None;
                 self.current += 1;
                 self.was_attached = true;
@@ -196,17 +206,19 @@ impl<'a> Parser<'a> {
                     .raw
                     .get(self.current)
                     .expect("`current` is valid if state is `Shorts`");
-                self.state = None;
-                self.current += 1;
-                if index == arg.len() {
+                let value = if index == arg.len() {
                     None
                 } else {
                     // SAFETY: everything preceding `index` were a short flags, making them valid UTF-8
                     let remainder = unsafe { ext::split_at(arg, index) }.1;
                     let remainder = remainder.strip_prefix("=").unwrap_or(remainder);
+                    self.mark_suffix(remainder);
                     self.was_attached = true;
                     Some(remainder)
-                }
+                };
+                self.state = None;
+                self.current += 1;
+                value
             }
             State::Escaped => None,
         }
@@ -269,15 +281,119 @@ impl<'a> Parser<'a> {
         debug_assert!(!self.was_attached);
 
         let next = self.raw.get(self.current)?;
+        self.mark(0..next.len());
         self.current += 1;
         Some(next)
     }
 
+    /// Save the current position, including any partially parsed argument
+    ///
+    /// This is cheap, allowing a parser to be tried speculatively, e.g.
+    /// ```
+    /// # use lexarg_parser::Arg::*;
+    /// let args = ["myapp", "-abc", "--exact"];
+    /// let mut parser = lexarg_parser::Parser::new(&args);
+    /// let _bin = parser.next_raw();
+    /// assert_eq!(parser.next_arg(), Some(Short("a")));
+    ///
+    /// let checkpoint = parser.checkpoint();
+    /// // A plugin takes `bc` as the value for `-a`, and then decides `-a` isn't for it
+    /// assert_eq!(parser.next_flag_value(), Some(std::ffi::OsStr::new("bc")));
+    /// parser.restore(checkpoint);
+    ///
+    /// assert_eq!(parser.next_arg(), Some(Short("b")));
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            current: self.current,
+            state: self.state,
+            was_attached: self.was_attached,
+            last: self.last,
+        }
+    }
+
+    /// Rewind to a [`Parser::checkpoint`]
+    ///
+    /// The `checkpoint` must have come from this parser.
+    pub fn restore(&mut self, checkpoint: Checkpoint<'a>) {
+        let Checkpoint {
+            current,
+            state,
+            was_attached,
+            last,
+        } = checkpoint;
+        debug_assert!(current <= self.raw.len(), "checkpoint from another parser");
+        self.current = current;
+        self.state = state;
+        self.was_attached = was_attached;
+        self.last = last;
+    }
+
+    /// Find where `arg` came from in the raw arguments, see [`Arg::locate`]
+    pub fn locate(&self, arg: Arg<'_>) -> Option<Location> {
+        arg.locate(self.raw)
+    }
+
+    /// Where the last [`Arg`] or value returned came from
+    ///
+    /// Unlike [`Parser::locate`], this still works after the [`Arg`] was rewritten, like when
+    /// resolving an abbreviation.
+    ///
+    /// ```
+    /// # use lexarg_parser::Arg::*;
+    /// let args = ["myapp", "-abc", "--exact"];
+    /// let mut parser = lexarg_parser::Parser::new(&args);
+    /// let _bin = parser.next_raw();
+    /// let _a = parser.next_arg();
+    /// let _b = parser.next_arg();
+    /// let location = parser.location().unwrap();
+    /// assert_eq!(location.index(), 1);
+    /// assert_eq!(location.range(), 2..3);
+    /// ```
+    pub fn location(&self) -> Option<Location> {
+        self.last.map(|span| Location {
+            index: span.index,
+            range: span.start..span.end,
+        })
+    }
+
+    /// Record `range` within the current raw argument as the last thing returned
+    fn mark(&mut self, range: std::ops::Range<usize>) {
+        self.last = Some(Span {
+            index: self.current,
+            start: range.start,
+            end: range.end,
+        });
+    }
+
+    /// Record `suffix` of the current raw argument as the last thing returned
+    fn mark_suffix(&mut self, suffix: &OsStr) {
+        let len = self.raw.get(self.current).map(|a| a.len()).unwrap_or(0);
+        self.mark(len - suffix.len()..len);
+    }
+
     fn has_pending(&self) -> bool {
         self.state.as_ref().map(State::has_pending).unwrap_or(false)
     }
 }
 
+/// A position within a [`Parser`], see [`Parser::checkpoint`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Checkpoint<'a> {
+    current: usize,
+    state: Option<State<'a>>,
+    was_attached: bool,
+    last: Option<Span>,
+}
+
+/// A [`Location`] that is `Copy`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Span {
+    index: usize,
+    start: usize,
+    end: usize,
+}
+
 /// Accessor for unparsed arguments
 pub trait RawArgs: std::fmt::Debug + private::Sealed {
     /// Returns a reference to an element or subslice depending on the type of index.
@@ -394,6 +510,68 @@ pub enum Arg<'a> {
     Unexpected(&'a OsStr),
 }
 
+impl Arg<'_> {
+    /// Find where this came from in `raw`
+    ///
+    /// Returns `None` if this was not borrowed from `raw`, like a placeholder
+    /// passed to an error.
+    ///
+    /// # Example
+    /// ```
+    /// let args = ["myapp", "-abc", "--exact"];
+    /// let mut parser = lexarg_parser::Parser::new(&args);
+    /// let _bin = parser.next_raw();
+    /// let _a = parser.next_arg();
+    /// let b = parser.next_arg().unwrap();
+    /// let location = b.locate(&args).unwrap();
+    /// assert_eq!(location.index(), 1);
+    /// assert_eq!(location.range(), 2..3);
+    /// ```
+    pub fn locate(&self, raw: &dyn RawArgs) -> Option<Location> {
+        let bytes = match self {
+            Self::Short(s) | Self::Long(s) | Self::Escape(s) => s.as_bytes(),
+            Self::Value(s) | Self::Unexpected(s) => s.as_encoded_bytes(),
+        };
+        let mut start = bytes.as_ptr() as usize;
+        let end = start + bytes.len();
+        if let Self::Long(_) = self {
+            // Include the `--`
+            start = start.saturating_sub(2);
+        }
+        (0..raw.len()).find_map(|index| {
+            let raw_bytes = raw.get(index)?.as_encoded_bytes();
+            let raw_start = raw_bytes.as_ptr() as usize;
+            let raw_end = raw_start + raw_bytes.len();
+            (raw_start <= start && end <= raw_end).then(|| Location {
+                index,
+                range: (start - raw_start)..(end - raw_start),
+            })
+        })
+    }
+}
+
+/// Where an [`Arg`] came from, see [`Arg::locate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    index: usize,
+    range: std::ops::Range<usize>,
+}
+
+impl Location {
+    /// Index of the raw argument
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Byte range within the raw argument
+    ///
+    /// This includes the leading `--` for [`Arg::Long`] but not the `-` for [`Arg::Short`] as
+    /// that may not be adjacent, like `-abc`.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.range.clone()
+    }
+}
+
 fn split_nonutf8_once(b: &OsStr) -> (&str, Option<&OsStr>) {
     match b.try_str() {
         Ok(s) => (s, None),
@@ -684,6 +862,107 @@ mod tests {
         assert_eq!(p.next_arg(), None);
     }
 
+    #[test]
+    fn locate() {
+        let args = ["-n", "10", "--foo=bar", "-xyz", "--", "baz"];
+        let mut p = Parser::new(&args);
+        let mut locations = Vec::new();
+        while let Some(arg) = p.next_arg() {
+            locations.push((arg, p.locate(arg).map(|l| (l.index(), l.range()))));
+            if arg == Short("n") || arg == Long("foo") {
+                let value = p.next_flag_value().unwrap();
+                let location = p.locate(Value(value)).map(|l| (l.index(), l.range()));
+                locations.push((Value(value), location));
+            }
+        }
+        assert_eq!(
+            locations,
+            [
+                (Short("n"), Some((0, 1..2))),
+                (Value(OsStr::new("10")), Some((1, 0..2))),
+                (Long("foo"), Some((2, 0..5))),
+                (Value(OsStr::new("bar")), Some((2, 6..9))),
+                (Short("x"), Some((3, 1..2))),
+                (Short("y"), Some((3, 2..3))),
+                (Short("z"), Some((3, 3..4))),
+                (Escape("--"), Some((4, 0..2))),
+                (Value(OsStr::new("baz")), Some((5, 0..3))),
+            ]
+        );
+        assert_eq!(p.locate(Value(OsStr::new("placeholder"))), None);
+    }
+
+    #[test]
+    fn location() {
+        let args = ["-n", "10", "--foo=bar", "-xyz", "-ab=c", "--", "baz"];
+        let mut p = Parser::new(&args);
+        let mut locations = Vec::new();
+        while let Some(arg) = p.next_arg() {
+            let location = p.location().map(|l| (l.index(), l.range()));
+            locations.push((arg, location));
+            if matches!(arg, Short("n") | Long("foo") | Short("b")) {
+                let value = p.next_flag_value().unwrap();
+                let location = p.location().map(|l| (l.index(), l.range()));
+                locations.push((Value(value), location));
+            }
+        }
+        assert_eq!(
+            locations,
+            [
+                (Short("n"), Some((0, 1..2))),
+                (Value(OsStr::new("10")), Some((1, 0..2))),
+                (Long("foo"), Some((2, 0..5))),
+                (Value(OsStr::new("bar")), Some((2, 6..9))),
+                (Short("x"), Some((3, 1..2))),
+                (Short("y"), Some((3, 2..3))),
+                (Short("z"), Some((3, 3..4))),
+                (Short("a"), Some((4, 1..2))),
+                (Short("b"), Some((4, 2..3))),
+                (Value(OsStr::new("c")), Some((4, 4..5))),
+                (Escape("--"), Some((5, 0..2))),
+                (Value(OsStr::new("baz")), Some((6, 0..3))),
+            ]
+        );
+
+        // Still found after being rewritten
+        let mut p = Parser::new(&["--ign"]);
+        assert_eq!(p.next_arg(), Some(Long("ign")));
+        let resolved = Long("ignored");
+        assert_eq!(p.locate(resolved), None);
+        assert_eq!(
+            p.location().map(|l| (l.index(), l.range())),
+            Some((0, 0..5))
+        );
+    }
+
+    #[test]
+    fn checkpoint() {
+        let args = ["-abc", "--foo=bar", "baz", "--", "-x"];
+        let mut p = Parser::new(&args);
+        let mut expected = Vec::new();
+        while let Some(arg) = p.next_arg() {
+            expected.push(arg);
+        }
+
+        // Speculatively consume values at every point, rewinding each time
+        let mut p = Parser::new(&args);
+        let mut actual = Vec::new();
+        loop {
+            let checkpoint = p.checkpoint();
+            let _ = p.next_flag_value();
+            let _ = p.next_arg();
+            let _ = p.next_raw();
+            p.restore(checkpoint);
+            assert_eq!(p.checkpoint(), checkpoint);
+
+            let Some(arg) = p.next_arg() else {
+                break;
+            };
+            actual.push(arg);
+        }
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn remaining_raw() {
         let mut p = Parser::new(&["-a", "b", "c", "d"]);
@@ -0,0 +1,66 @@
+use lexarg::LexError;
+use lexarg::Result;
+
+/// Same as `hello`, but with the parser generated
+#[derive(lexarg::Parse)]
+struct Args {
+    /// What to greet
+    #[arg(positional)]
+    thing: String,
+    /// Number of times to greet
+    #[arg(short = "n", value = "NUM", default = 1)]
+    number: u32,
+    /// Use all caps
+    shout: bool,
+}
+
+fn parse_args() -> Result<Args> {
+    use lexarg::prelude::*;
+
+    let mut args = ArgsBuilder::new();
+    let raw = std::env::args_os().collect::<Vec<_>>();
+    let mut parser = lexarg::Parser::new(&raw);
+    let bin_name = parser
+        .next_raw()
+        .expect("nothing parsed yet so no attached lingering")
+        .expect("always at least one");
+    let mut prev_arg = Value(bin_name);
+    while let Some(arg) = parser.next_arg() {
+        match arg {
+            Short("h") | Long("help") => {
+                let bin_name = bin_name.to_string_lossy();
+                print!(
+                    "{}",
+                    lexarg::Help::new(&bin_name).specs(ArgsBuilder::OPTIONS)
+                );
+                std::process::exit(0);
+            }
+            Unexpected(_) => {
+                return Err(LexError::msg("unexpected value")
+                    .unexpected(arg)
+                    .within(prev_arg)
+                    .into());
+            }
+            _ => {}
+        }
+        prev_arg = arg;
+
+        if let Some(arg) = args.parse_next(&mut parser, arg)? {
+            return Err(LexError::msg("unexpected argument").unexpected(arg).into());
+        }
+    }
+
+    Ok(args.finish().within(Value(bin_name))?)
+}
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    let mut message = format!("Hello {}", args.thing);
+    if args.shout {
+        message = message.to_uppercase();
+    }
+    for _ in 0..args.number {
+        println!("{message}");
+    }
+    Ok(())
+}
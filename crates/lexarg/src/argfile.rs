@@ -0,0 +1,186 @@
+/// Expand `@path` arguments into the arguments read from `path`
+///
+/// Syntax:
+/// - Each line is one argument, taken verbatim, like with `rustc`
+/// - Blank lines and lines starting with `#` are skipped
+/// - Lines with quotes are instead split into arguments on whitespace, where
+///   - `'...'` is taken literally
+///   - `"..."` allows `\"` and `\\` escapes
+///   - `#` at the start of an argument comments out the rest of the line
+/// - `\` outside of quotes is taken literally, like in `C:\dir\file`
+/// - An unquoted argument starting with `@` is expanded recursively, relative to the directory of
+///   the file it is in
+///
+/// Errors report the file and line they occurred on.
+///
+/// ```rust
+/// let args = lexarg::expand_args(["bin", "--exact", "one"]).unwrap();
+/// assert_eq!(args, ["bin", "--exact", "one"]);
+/// ```
+pub fn expand_args(
+    args: impl IntoIterator<Item = impl Into<std::ffi::OsString>>,
+) -> std::io::Result<Vec<std::ffi::OsString>> {
+    let mut expanded = Vec::new();
+    for arg in args {
+        let arg = arg.into();
+        if let Some(path) = arg.to_str().and_then(|s| s.strip_prefix('@')) {
+            let path = std::path::Path::new(path);
+            let content = read(path)?;
+            let mut stack = Vec::new();
+            expand_file(path, &content, &mut stack, &mut expanded)?;
+        } else {
+            expanded.push(arg);
+        }
+    }
+    Ok(expanded)
+}
+
+fn read(path: &std::path::Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| std::io::Error::new(err.kind(), format!("{}: {err}", path.display())))
+}
+
+fn expand_file(
+    path: &std::path::Path,
+    content: &str,
+    stack: &mut Vec<std::path::PathBuf>,
+    expanded: &mut Vec<std::ffi::OsString>,
+) -> std::io::Result<()> {
+    let canonical = path.canonicalize()?;
+    stack.push(canonical);
+
+    for word in split(content).map_err(|(line, msg)| syntax_error(path, line, msg))? {
+        let nested = if word.quoted {
+            None
+        } else {
+            word.value.strip_prefix('@')
+        };
+        if let Some(nested) = nested {
+            let nested = path
+                .parent()
+                .unwrap_or_else(|| std::path::Path::new(""))
+                .join(nested);
+            let is_cycle = nested
+                .canonicalize()
+                .is_ok_and(|nested| stack.contains(&nested));
+            if is_cycle {
+                return Err(syntax_error(
+                    path,
+                    word.line,
+                    format!("`@{}` includes itself", nested.display()),
+                ));
+            }
+            let content = read(&nested).map_err(|err| {
+                std::io::Error::new(
+                    err.kind(),
+                    format!("{}:{}: {err}", path.display(), word.line),
+                )
+            })?;
+            expand_file(&nested, &content, stack, expanded)?;
+        } else {
+            expanded.push(word.value.into());
+        }
+    }
+
+    stack.pop();
+    Ok(())
+}
+
+fn syntax_error(
+    path: &std::path::Path,
+    line: usize,
+    msg: impl std::fmt::Display,
+) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("{}:{line}: {msg}", path.display()),
+    )
+}
+
+struct Word {
+    value: String,
+    /// Line the word is on, 1-indexed
+    line: usize,
+    /// Whether the word started with a quote, preventing `@` expansion
+    quoted: bool,
+}
+
+/// Split `content` into words, reporting the 1-indexed line of any error
+fn split(content: &str) -> Result<Vec<Word>, (usize, &'static str)> {
+    let mut words = Vec::new();
+    for (i, text) in content.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = text.trim_start();
+        if trimmed.trim_end().is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if text.contains(['\'', '"']) {
+            split_line(text, line, &mut words)?;
+        } else {
+            words.push(Word {
+                value: text.to_owned(),
+                line,
+                quoted: false,
+            });
+        }
+    }
+    Ok(words)
+}
+
+/// Split a line with quotes into words, shell-style
+fn split_line(text: &str, line: usize, words: &mut Vec<Word>) -> Result<(), (usize, &'static str)> {
+    let mut current: Option<Word> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                words.extend(current.take());
+            }
+            '#' if current.is_none() => break,
+            '\'' => {
+                let word = current.get_or_insert_with(|| Word::new(line, true));
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.value.push(c),
+                        None => return Err((line, "unterminated `'`")),
+                    }
+                }
+            }
+            '"' => {
+                let word = current.get_or_insert_with(|| Word::new(line, true));
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.value.push(c),
+                            Some(c) => {
+                                word.value.push('\\');
+                                word.value.push(c);
+                            }
+                            None => return Err((line, "unterminated `\"`")),
+                        },
+                        Some(c) => word.value.push(c),
+                        None => return Err((line, "unterminated `\"`")),
+                    }
+                }
+            }
+            c => {
+                let word = current.get_or_insert_with(|| Word::new(line, false));
+                word.value.push(c);
+            }
+        }
+    }
+    words.extend(current);
+    Ok(())
+}
+
+impl Word {
+    fn new(line: usize, quoted: bool) -> Self {
+        Self {
+            value: String::new(),
+            line,
+            quoted,
+        }
+    }
+}
@@ -0,0 +1,375 @@
+/// Declaration of an argument, for generating [`Help`]
+///
+/// This is only descriptive; parsing is still done by matching on [`Arg`][crate::Arg].
+///
+/// ```rust
+/// const OPTIONS: &[lexarg::OptSpec] = &[
+///     lexarg::OptSpec::positional("THING").required().help("What to greet"),
+///     lexarg::OptSpec::new()
+///         .short("n")
+///         .long("number")
+///         .value("NUM")
+///         .help("Number of times to greet"),
+///     lexarg::OptSpec::new().long("shout").help("Use all caps"),
+/// ];
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OptSpec {
+    short: Option<&'static str>,
+    long: Option<&'static str>,
+    value: Option<&'static str>,
+    help: &'static str,
+    possible: &'static [PossibleValue],
+    required: bool,
+    multiple: bool,
+    positional: bool,
+}
+
+impl OptSpec {
+    /// Declare a flag, see [`OptSpec::short`] and [`OptSpec::long`]
+    pub const fn new() -> Self {
+        Self {
+            short: None,
+            long: None,
+            value: None,
+            help: "",
+            possible: &[],
+            required: false,
+            multiple: false,
+            positional: false,
+        }
+    }
+
+    /// Declare a positional [`Arg::Value`][crate::Arg::Value]
+    pub const fn positional(value: &'static str) -> Self {
+        let mut spec = Self::new().value(value);
+        spec.positional = true;
+        spec
+    }
+
+    /// Name for [`Arg::Short`][crate::Arg::Short], without the leading `-`
+    pub const fn short(mut self, short: &'static str) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    /// Name for [`Arg::Long`][crate::Arg::Long], without the leading `--`
+    pub const fn long(mut self, long: &'static str) -> Self {
+        self.long = Some(long);
+        self
+    }
+
+    /// Placeholder for the value the flag takes, like `NUM`
+    pub const fn value(mut self, value: &'static str) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Description of the argument
+    ///
+    /// Each line is wrapped and aligned separately.
+    pub const fn help(mut self, help: &'static str) -> Self {
+        self.help = help;
+        self
+    }
+
+    /// The closed set of values the argument accepts
+    ///
+    /// See also [`ValueExt::one_of`][crate::ValueExt::one_of]
+    pub const fn possible(mut self, possible: &'static [PossibleValue]) -> Self {
+        self.possible = possible;
+        self
+    }
+
+    /// The argument must be present
+    pub const fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// The argument may be present more than once
+    pub const fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// See [`OptSpec::short`]
+    pub fn get_short(&self) -> Option<&'static str> {
+        self.short
+    }
+
+    /// See [`OptSpec::long`]
+    pub fn get_long(&self) -> Option<&'static str> {
+        self.long
+    }
+
+    /// See [`OptSpec::value`]
+    pub fn get_value(&self) -> Option<&'static str> {
+        self.value
+    }
+
+    /// See [`OptSpec::help`]
+    pub fn get_help(&self) -> &'static str {
+        self.help
+    }
+
+    /// See [`OptSpec::possible`]
+    pub fn get_possible(&self) -> &'static [PossibleValue] {
+        self.possible
+    }
+
+    /// See [`OptSpec::required`]
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// See [`OptSpec::multiple`]
+    pub fn is_multiple(&self) -> bool {
+        self.multiple
+    }
+
+    /// See [`OptSpec::positional`]
+    pub fn is_positional(&self) -> bool {
+        self.positional
+    }
+
+    fn render_name(&self) -> String {
+        if self.positional {
+            let value = self.value.unwrap_or("ARG");
+            let mut name = if self.required {
+                format!("<{value}>")
+            } else {
+                format!("[{value}]")
+            };
+            if self.multiple {
+                name.push_str("...");
+            }
+            return name;
+        }
+
+        let mut name = String::new();
+        match (self.short, self.long) {
+            (Some(short), Some(long)) => name.push_str(&format!("-{short}, --{long}")),
+            (Some(short), None) => name.push_str(&format!("-{short}")),
+            (None, Some(long)) => name.push_str(&format!("    --{long}")),
+            (None, None) => {}
+        }
+        if !self.possible.is_empty() {
+            let possible = self
+                .possible
+                .iter()
+                .map(|p| p.name)
+                .collect::<Vec<_>>()
+                .join("|");
+            name.push_str(&format!(" <{possible}>"));
+        } else if let Some(value) = self.value {
+            name.push_str(&format!(" {value}"));
+        }
+        name
+    }
+
+    fn render_help(&self) -> Vec<String> {
+        let mut lines = self.help.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+        for possible in self.possible {
+            if !possible.help.is_empty() {
+                lines.push(format!("- {}: {}", possible.name, possible.help));
+            }
+        }
+        if self.multiple && !self.positional {
+            lines.push("(this flag can be used multiple times)".to_owned());
+        }
+        lines
+    }
+}
+
+impl Default for OptSpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value accepted by an [`OptSpec`], see [`OptSpec::possible`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PossibleValue {
+    name: &'static str,
+    help: &'static str,
+}
+
+impl PossibleValue {
+    /// Declare a value
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, help: "" }
+    }
+
+    /// Description of the value
+    pub const fn help(mut self, help: &'static str) -> Self {
+        self.help = help;
+        self
+    }
+
+    /// See [`PossibleValue::new`]
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// See [`PossibleValue::help`]
+    pub fn get_help(&self) -> &'static str {
+        self.help
+    }
+}
+
+/// Render `--help` from the [`OptSpec`]s of each participating parser
+///
+/// ```rust
+/// const OPTIONS: &[lexarg::OptSpec] = &[
+///     lexarg::OptSpec::positional("THING").required().help("What to greet"),
+///     lexarg::OptSpec::new()
+///         .short("n")
+///         .long("number")
+///         .value("NUM")
+///         .help("Number of times to greet"),
+///     lexarg::OptSpec::new().long("shout").help("Use all caps"),
+/// ];
+///
+/// let help = lexarg::Help::new("hello").specs(OPTIONS).to_string();
+/// assert_eq!(help, "\
+/// Usage: hello [OPTIONS] <THING>
+///
+/// Arguments:
+///       <THING>         What to greet
+///
+/// Options:
+///   -n, --number NUM    Number of times to greet
+///       --shout         Use all caps
+/// ");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Help<'a> {
+    bin: &'a str,
+    specs: Vec<OptSpec>,
+    after_help: Vec<&'a str>,
+}
+
+impl<'a> Help<'a> {
+    /// Help for `bin`
+    pub fn new(bin: &'a str) -> Self {
+        Self {
+            bin,
+            specs: Vec::new(),
+            after_help: Vec::new(),
+        }
+    }
+
+    /// Add the arguments for a parser
+    ///
+    /// Arguments are listed in the order they are added.
+    pub fn specs(mut self, specs: &[OptSpec]) -> Self {
+        self.specs.extend_from_slice(specs);
+        self
+    }
+
+    /// The `Usage:` line, like for [`LexError::render`][crate::LexError::render]
+    pub fn usage(&self) -> String {
+        let mut usage = format!("Usage: {}", self.bin);
+        if self.specs.iter().any(|s| !s.is_positional()) {
+            usage.push_str(" [OPTIONS]");
+        }
+        for positional in self.specs.iter().filter(|s| s.is_positional()) {
+            usage.push(' ');
+            usage.push_str(&positional.render_name());
+        }
+        usage
+    }
+
+    /// Add a paragraph to show after the arguments
+    ///
+    /// Empty paragraphs are skipped.
+    pub fn after_help(mut self, after_help: &'a str) -> Self {
+        let after_help = after_help.trim();
+        if !after_help.is_empty() {
+            self.after_help.push(after_help);
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for Help<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (positionals, options): (Vec<&OptSpec>, Vec<&OptSpec>) =
+            self.specs.iter().partition(|s| s.is_positional());
+
+        writeln!(f, "{}", self.usage())?;
+
+        if !positionals.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Arguments:")?;
+            for positional in &positionals {
+                write_spec(f, positional)?;
+            }
+        }
+
+        if !options.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Options:")?;
+            for option in &options {
+                write_spec(f, option)?;
+            }
+        }
+
+        for after_help in &self.after_help {
+            writeln!(f)?;
+            writeln!(f, "{after_help}")?;
+        }
+
+        Ok(())
+    }
+}
+
+const NAME_INDENT: usize = 2;
+const HELP_INDENT: usize = 22;
+const WIDTH: usize = 100;
+
+fn write_spec(f: &mut std::fmt::Formatter<'_>, spec: &OptSpec) -> std::fmt::Result {
+    let mut name = spec.render_name();
+    if spec.is_positional() {
+        // Align with long flags
+        name.insert_str(0, "    ");
+    }
+    let name = format!("{:NAME_INDENT$}{name}", "");
+
+    let mut lines = spec
+        .render_help()
+        .into_iter()
+        .flat_map(|line| wrap(&line, WIDTH - HELP_INDENT));
+    if name.len() + 2 <= HELP_INDENT {
+        if let Some(first) = lines.next() {
+            writeln!(f, "{name:HELP_INDENT$}{first}")?;
+        } else {
+            writeln!(f, "{name}")?;
+        }
+    } else {
+        writeln!(f, "{name}")?;
+    }
+    for line in lines {
+        writeln!(f, "{:HELP_INDENT$}{line}", "")?;
+    }
+    Ok(())
+}
+
+/// Break `line` on whitespace so each piece fits within `width`
+///
+/// Words longer than `width` are left intact.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        if !current.is_empty() && width < current.len() + 1 + word.len() {
+            lines.push(std::mem::take(&mut current));
+        } else if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
@@ -24,8 +24,21 @@ pub mod prelude {
     pub use crate::ValueExt as _;
 }
 
+mod argfile;
+mod help;
+
+pub use argfile::expand_args;
+pub use help::Help;
+pub use help::OptSpec;
+pub use help::PossibleValue;
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use lexarg_derive::Parse;
 pub use lexarg_error::LexError;
+pub use lexarg_error::Rendered;
 pub use lexarg_parser::Arg;
+pub use lexarg_parser::Checkpoint;
+pub use lexarg_parser::Location;
 pub use lexarg_parser::Parser;
 pub use lexarg_parser::RawArgs;
 
@@ -101,6 +114,9 @@ impl<'a> ValueExt<'a> for &'a std::ffi::OsStr {
         self.to_str()
             .filter(|v| possible.contains(v))
             .ok_or_else(|| {
+                let suggestion = self
+                    .to_str()
+                    .and_then(|v| did_you_mean(v, possible.iter().copied()));
                 let mut possible = possible.iter();
                 let first = possible.next().expect("at least one possible value");
                 let mut error = format!("expected one of `{first}`");
@@ -108,7 +124,12 @@ impl<'a> ValueExt<'a> for &'a std::ffi::OsStr {
                     use std::fmt::Write as _;
                     let _ = write!(&mut error, ", `{possible}`");
                 }
-                LexError::msg(error)
+                let error = LexError::msg(error).unexpected(Arg::Value(self));
+                if let Some(suggestion) = suggestion {
+                    error.suggest_value(suggestion)
+                } else {
+                    error
+                }
             })
     }
     fn parse<T: std::str::FromStr>(self) -> Result<T, LexError<'a>>
@@ -187,6 +208,120 @@ impl<T> OptionLexErrorExt<T> for Option<T> {
     }
 }
 
+/// Find the closest match to `unexpected` among `possible`
+///
+/// Returns `None` if nothing is close enough to be a likely typo.
+///
+/// ```rust
+/// let possible = ["include-ignored", "ignored", "exact"];
+/// assert_eq!(lexarg::did_you_mean("include-ignore", possible), Some("include-ignored"));
+/// assert_eq!(lexarg::did_you_mean("bench", possible), None);
+/// ```
+pub fn did_you_mean<'p>(
+    unexpected: &str,
+    possible: impl IntoIterator<Item = &'p str>,
+) -> Option<&'p str> {
+    possible
+        .into_iter()
+        .map(|p| (edit_distance(unexpected, p), p))
+        .filter(|(distance, p)| {
+            let threshold = (unexpected.chars().count().max(p.chars().count()) / 3).max(1);
+            *distance <= threshold
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, p)| p)
+}
+
+/// Suggest a similar long flag from `specs` for an unknown [`Arg::Long`]
+///
+/// The suggestion is attached to `error`.
+pub fn suggest_long<'a>(error: LexError<'a>, arg: Arg<'_>, specs: &[OptSpec]) -> LexError<'a> {
+    let Arg::Long(unexpected) = arg else {
+        return error;
+    };
+    let possible = specs.iter().filter_map(|s| s.get_long());
+    match did_you_mean(unexpected, possible) {
+        Some(suggestion) => error.suggest(format_args!("--{suggestion}")),
+        None => error,
+    }
+}
+
+/// Resolve an unambiguous prefix of a long flag in `specs` to the full [`Arg::Long`]
+///
+/// This is opt-in, for `getopts`-style abbreviations like `--ign` for `--ignored`.
+/// When parsers cooperate, call this before dispatching with the [`OptSpec`]s of every parser so a
+/// prefix is only resolved when it is unambiguous among all of them.
+///
+/// Exact matches and unknown flags are returned as-is.  If the prefix matches multiple flags, the
+/// error lists them.
+///
+/// ```rust
+/// use lexarg::Arg::Long;
+///
+/// const OPTIONS: &[lexarg::OptSpec] = &[
+///     lexarg::OptSpec::new().long("test"),
+///     lexarg::OptSpec::new().long("test-threads").value("NUM"),
+///     lexarg::OptSpec::new().long("ignored"),
+/// ];
+///
+/// assert_eq!(lexarg::resolve_prefix(Long("ign"), OPTIONS).unwrap(), Long("ignored"));
+/// assert_eq!(lexarg::resolve_prefix(Long("test"), OPTIONS).unwrap(), Long("test"));
+/// assert_eq!(lexarg::resolve_prefix(Long("bench"), OPTIONS).unwrap(), Long("bench"));
+/// assert_eq!(
+///     lexarg::resolve_prefix(Long("te"), OPTIONS).unwrap_err().to_string(),
+///     "ambiguous argument, could be `--test` or `--test-threads`, found `--te`"
+/// );
+/// ```
+pub fn resolve_prefix<'a>(arg: Arg<'a>, specs: &[OptSpec]) -> Result<Arg<'a>, LexError<'a>> {
+    let Arg::Long(prefix) = arg else {
+        return Ok(arg);
+    };
+    if prefix.is_empty() {
+        return Ok(arg);
+    }
+
+    let mut candidates = Vec::new();
+    for long in specs.iter().filter_map(|s| s.get_long()) {
+        if long == prefix {
+            return Ok(arg);
+        }
+        if long.starts_with(prefix) && !candidates.contains(&long) {
+            candidates.push(long);
+        }
+    }
+    match candidates.as_slice() {
+        [] => Ok(arg),
+        [long] => Ok(Arg::Long(long)),
+        [init @ .., last] => {
+            let init = init
+                .iter()
+                .map(|c| format!("`--{c}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(LexError::msg(format_args!(
+                "ambiguous argument, could be {init} or `--{last}`"
+            ))
+            .unexpected(arg))
+        }
+    }
+}
+
+/// Levenshtein distance
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut prev = (0..=rhs.len()).collect::<Vec<_>>();
+    let mut current = vec![0; rhs.len() + 1];
+    for (i, l) in lhs.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, r) in rhs.iter().enumerate() {
+            let substitution = prev[j] + usize::from(l != *r);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+    prev[rhs.len()]
+}
+
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
 pub struct ReadmeDoctests;
@@ -10,8 +10,12 @@ pub enum Event {
     RunStart(RunStart),
     CaseStart(CaseStart),
     CaseMessage(CaseMessage),
+    CaseOutput(CaseOutput),
+    CaseAttachment(CaseAttachment),
     CaseComplete(CaseComplete),
+    RunMessage(RunMessage),
     RunComplete(RunComplete),
+    Extension(Extension),
 }
 
 impl Event {
@@ -24,8 +28,12 @@ impl Event {
             Self::RunStart(event) => event.to_jsonline(),
             Self::CaseStart(event) => event.to_jsonline(),
             Self::CaseMessage(event) => event.to_jsonline(),
+            Self::CaseOutput(event) => event.to_jsonline(),
+            Self::CaseAttachment(event) => event.to_jsonline(),
             Self::CaseComplete(event) => event.to_jsonline(),
+            Self::RunMessage(event) => event.to_jsonline(),
             Self::RunComplete(event) => event.to_jsonline(),
+            Self::Extension(event) => event.to_jsonline(),
         }
     }
 }
@@ -66,12 +74,30 @@ impl From<CaseMessage> for Event {
     }
 }
 
+impl From<CaseOutput> for Event {
+    fn from(inner: CaseOutput) -> Self {
+        Self::CaseOutput(inner)
+    }
+}
+
+impl From<CaseAttachment> for Event {
+    fn from(inner: CaseAttachment) -> Self {
+        Self::CaseAttachment(inner)
+    }
+}
+
 impl From<CaseComplete> for Event {
     fn from(inner: CaseComplete) -> Self {
         Self::CaseComplete(inner)
     }
 }
 
+impl From<RunMessage> for Event {
+    fn from(inner: RunMessage) -> Self {
+        Self::RunMessage(inner)
+    }
+}
+
 impl From<RunComplete> for Event {
     fn from(inner: RunComplete) -> Self {
         Self::RunComplete(inner)
@@ -136,6 +162,12 @@ pub struct DiscoverCase {
         serde(default = "true_default", skip_serializing_if = "is_true")
     )]
     pub selected: bool,
+    /// Labels for grouping cases, in the order declared
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -174,6 +206,20 @@ impl DiscoverCase {
             buffer.value(self.selected).unwrap();
         }
 
+        if !self.tags.is_empty() {
+            buffer.val_sep().unwrap();
+            buffer.key("tags").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.open_array().unwrap();
+            for (i, tag) in self.tags.iter().enumerate() {
+                if i != 0 {
+                    buffer.val_sep().unwrap();
+                }
+                buffer.value(tag).unwrap();
+            }
+            buffer.close_array().unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -192,6 +238,12 @@ impl DiscoverCase {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct DiscoverComplete {
+    /// Seed the selected cases were shuffled with, see `--shuffle-seed`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub shuffle_seed: Option<u64>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -211,6 +263,13 @@ impl DiscoverComplete {
         buffer.keyval_sep().unwrap();
         buffer.value("discover_complete").unwrap();
 
+        if let Some(shuffle_seed) = self.shuffle_seed {
+            buffer.val_sep().unwrap();
+            buffer.key("shuffle_seed").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(shuffle_seed).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -229,6 +288,12 @@ impl DiscoverComplete {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RunStart {
+    /// Seed that each case's randomness is derived from, see `--seed`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub seed: Option<u64>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -248,6 +313,13 @@ impl RunStart {
         buffer.keyval_sep().unwrap();
         buffer.value("run_start").unwrap();
 
+        if let Some(seed) = self.seed {
+            buffer.val_sep().unwrap();
+            buffer.key("seed").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(seed).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -367,6 +439,136 @@ impl CaseMessage {
     }
 }
 
+/// A chunk of output captured from a test case
+///
+/// A case may report any number of these; consumers should concatenate the chunks for each
+/// [`OutputStream`] in the order they are received.
+///
+/// Whether output is captured depends on the harness, e.g. one that can't intercept `print!`
+/// only reports what the test hands it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct CaseOutput {
+    /// An identifier that is unique across the entire run
+    pub name: String,
+    pub stream: OutputStream,
+    pub content: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub elapsed_s: Option<Elapsed>,
+}
+
+impl CaseOutput {
+    #[cfg(feature = "json")]
+    pub fn to_jsonline(&self) -> String {
+        use json_write::JsonWrite as _;
+
+        let mut buffer = String::new();
+        buffer.open_object().unwrap();
+
+        buffer.key("event").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value("case_output").unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("name").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.name).unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("stream").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(self.stream.as_str()).unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("content").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.content).unwrap();
+
+        if let Some(elapsed_s) = self.elapsed_s {
+            buffer.val_sep().unwrap();
+            buffer.key("elapsed_s").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(elapsed_s)).unwrap();
+        }
+
+        buffer.close_object().unwrap();
+
+        buffer
+    }
+}
+
+/// A file artifact published by a test case, like a snapshot or screenshot
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct CaseAttachment {
+    /// An identifier that is unique across the entire run
+    pub name: String,
+    /// An identifier that is unique within the case
+    pub attachment: String,
+    /// Absolute path to the content
+    pub path: String,
+    /// The [media type](https://www.iana.org/assignments/media-types/media-types.xhtml) of the
+    /// content, e.g. `image/png`
+    pub media_type: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub elapsed_s: Option<Elapsed>,
+}
+
+impl CaseAttachment {
+    #[cfg(feature = "json")]
+    pub fn to_jsonline(&self) -> String {
+        use json_write::JsonWrite as _;
+
+        let mut buffer = String::new();
+        buffer.open_object().unwrap();
+
+        buffer.key("event").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value("case_attachment").unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("name").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.name).unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("attachment").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.attachment).unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("path").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.path).unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("media_type").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.media_type).unwrap();
+
+        if let Some(elapsed_s) = self.elapsed_s {
+            buffer.val_sep().unwrap();
+            buffer.key("elapsed_s").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(elapsed_s)).unwrap();
+        }
+
+        buffer.close_object().unwrap();
+
+        buffer
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -374,6 +576,12 @@ impl CaseMessage {
 pub struct CaseComplete {
     /// An identifier that is unique across the entire run
     pub name: String,
+    /// Seed the case derived its randomness from, if it used any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub seed: Option<u64>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -398,6 +606,71 @@ impl CaseComplete {
         buffer.keyval_sep().unwrap();
         buffer.value(&self.name).unwrap();
 
+        if let Some(seed) = self.seed {
+            buffer.val_sep().unwrap();
+            buffer.key("seed").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(seed).unwrap();
+        }
+
+        if let Some(elapsed_s) = self.elapsed_s {
+            buffer.val_sep().unwrap();
+            buffer.key("elapsed_s").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(elapsed_s)).unwrap();
+        }
+
+        buffer.close_object().unwrap();
+
+        buffer
+    }
+}
+
+/// A status update for the run as a whole, rather than any one case
+///
+/// For example, a harness reports setup for all cases failing with this.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct RunMessage {
+    pub kind: MessageKind,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub message: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub elapsed_s: Option<Elapsed>,
+}
+
+impl RunMessage {
+    #[cfg(feature = "json")]
+    pub fn to_jsonline(&self) -> String {
+        use json_write::JsonWrite as _;
+
+        let mut buffer = String::new();
+        buffer.open_object().unwrap();
+
+        buffer.key("event").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value("run_message").unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("kind").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(self.kind.as_str()).unwrap();
+
+        if let Some(message) = &self.message {
+            buffer.val_sep().unwrap();
+            buffer.key("message").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(message).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -448,6 +721,162 @@ impl RunComplete {
     }
 }
 
+impl From<Extension> for Event {
+    fn from(inner: Extension) -> Self {
+        Self::Extension(inner)
+    }
+}
+
+/// Custom data reported by a test harness built on top of libtest
+///
+/// Runners should skip extensions they do not recognize.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct Extension {
+    /// Namespaced identifier for the type of extension, e.g. `trybuild.compile_fail`
+    ///
+    /// By convention, this is prefixed by the name of the crate defining the extension.
+    pub extension: String,
+    /// The case this is reported for, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+    /// Arbitrary JSON value, defined by the extension
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "unstable-schema",
+        schemars(with = "Option<serde_json::Value>")
+    )]
+    pub payload: Option<Payload>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub elapsed_s: Option<Elapsed>,
+}
+
+impl Extension {
+    /// Report [`Extension::payload`] as written
+    #[cfg(feature = "json")]
+    pub fn to_jsonline(&self) -> String {
+        use json_write::JsonWrite as _;
+        use std::fmt::Write as _;
+
+        let mut buffer = String::new();
+        buffer.open_object().unwrap();
+
+        buffer.key("event").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value("extension").unwrap();
+
+        buffer.val_sep().unwrap();
+        buffer.key("extension").unwrap();
+        buffer.keyval_sep().unwrap();
+        buffer.value(&self.extension).unwrap();
+
+        if let Some(name) = &self.name {
+            buffer.val_sep().unwrap();
+            buffer.key("name").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(name).unwrap();
+        }
+
+        if let Some(payload) = &self.payload {
+            buffer.val_sep().unwrap();
+            buffer.key("payload").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.write_str(payload.as_str()).unwrap();
+        }
+
+        if let Some(elapsed_s) = self.elapsed_s {
+            buffer.val_sep().unwrap();
+            buffer.key("elapsed_s").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(elapsed_s)).unwrap();
+        }
+
+        buffer.close_object().unwrap();
+
+        buffer
+    }
+}
+
+/// A JSON value, kept as written, see [`Extension::payload`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payload(String);
+
+impl Payload {
+    /// Check that `json` is a single JSON value
+    ///
+    /// Surrounding whitespace is trimmed and line breaks between tokens are replaced with spaces,
+    /// keeping the event on one line.  Everything else is reported as written.
+    pub fn new(json: impl Into<String>) -> Result<Self, InvalidPayload> {
+        let json = json.into();
+        if !crate::raw_json::is_valid(&json) {
+            return Err(InvalidPayload(()));
+        }
+        let mut json = json.trim().to_owned();
+        if json.contains(['\n', '\r']) {
+            // Strings can't contain raw line breaks, so these can only be whitespace
+            json = json.replace(['\n', '\r'], " ");
+        }
+        Ok(Self(json))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Payload {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Payload {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde_json::value::RawValue::from_string(self.0.clone())
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Payload {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // `RawValue` can't be used within internally tagged enums, so numbers go through `f64`
+        let payload = serde_json::Value::deserialize(deserializer)?;
+        Ok(Self(payload.to_string()))
+    }
+}
+
+/// See [`Payload::new`]
+#[derive(Clone, Debug)]
+pub struct InvalidPayload(());
+
+impl std::fmt::Display for InvalidPayload {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        "payload is not valid JSON".fmt(formatter)
+    }
+}
+
+impl std::error::Error for InvalidPayload {}
+
 #[cfg(feature = "serde")]
 fn true_default() -> bool {
     true
@@ -488,6 +917,8 @@ impl RunMode {
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MessageKind {
     // Highest precedent items for determining test status last
+    /// A fixture the case depends on failed to set up or tear down
+    Fixture,
     Error,
     Ignored,
 }
@@ -495,12 +926,31 @@ pub enum MessageKind {
 impl MessageKind {
     pub fn as_str(&self) -> &str {
         match self {
+            Self::Fixture => "fixture",
             Self::Error => "error",
             Self::Ignored => "ignored",
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
 /// Time elapsed since process start
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
@@ -5,11 +5,16 @@
 #![warn(clippy::print_stdout)]
 
 pub mod event;
+pub mod report;
+
+mod raw_json;
 
 pub use event::Elapsed;
 pub use event::Event;
 pub use event::MessageKind;
+pub use event::OutputStream;
 pub use event::RunMode;
+pub use report::RunReport;
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
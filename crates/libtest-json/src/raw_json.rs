@@ -0,0 +1,185 @@
+//! Check that text is JSON, see [`Payload`][crate::event::Payload]
+
+/// Nesting beyond this is rejected, rather than overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+/// Whether `input` is a single JSON value, surrounded by optional whitespace
+pub(crate) fn is_valid(input: &str) -> bool {
+    let mut validator = Validator {
+        input: input.as_bytes(),
+        pos: 0,
+    };
+    validator.value(0).is_some() && {
+        validator.whitespace();
+        validator.pos == validator.input.len()
+    }
+}
+
+struct Validator<'i> {
+    input: &'i [u8],
+    pos: usize,
+}
+
+impl Validator<'_> {
+    fn value(&mut self, depth: usize) -> Option<()> {
+        if MAX_DEPTH < depth {
+            return None;
+        }
+        self.whitespace();
+        match self.peek()? {
+            b'n' => self.keyword("null"),
+            b't' => self.keyword("true"),
+            b'f' => self.keyword("false"),
+            b'"' => self.string(),
+            b'[' => self.items(b']', |v| v.value(depth + 1)),
+            b'{' => self.items(b'}', |v| {
+                v.whitespace();
+                v.string()?;
+                v.whitespace();
+                v.expect(b':')?;
+                v.value(depth + 1)
+            }),
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => None,
+        }
+    }
+
+    /// Comma-separated `item`s until `close`, after the opening bracket
+    fn items(&mut self, close: u8, mut item: impl FnMut(&mut Self) -> Option<()>) -> Option<()> {
+        self.pos += 1;
+        self.whitespace();
+        if self.eat(close) {
+            return Some(());
+        }
+        loop {
+            item(self)?;
+            self.whitespace();
+            if self.eat(close) {
+                return Some(());
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Option<()> {
+        let end = self.pos + keyword.len();
+        (self.input.get(self.pos..end)? == keyword.as_bytes()).then(|| self.pos = end)
+    }
+
+    fn number(&mut self) -> Option<()> {
+        self.eat(b'-');
+        if !self.eat(b'0') {
+            self.digits()?;
+        }
+        if self.eat(b'.') {
+            self.digits()?;
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            let _ = self.eat(b'+') || self.eat(b'-');
+            self.digits()?;
+        }
+        Some(())
+    }
+
+    /// One or more digits
+    fn digits(&mut self) -> Option<()> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        (start < self.pos).then_some(())
+    }
+
+    fn string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    match self.peek()? {
+                        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => self.pos += 1,
+                        b'u' => {
+                            let hex = self.input.get(self.pos + 1..self.pos + 5)?;
+                            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                                return None;
+                            }
+                            self.pos += 5;
+                        }
+                        _ => return None,
+                    }
+                }
+                b if b < b' ' => return None,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let eaten = self.peek() == Some(b);
+        if eaten {
+            self.pos += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        self.eat(b).then_some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        for input in [
+            "null",
+            " true ",
+            "-0.5e+10",
+            "1e400",
+            r#""a\"é""#,
+            "[]",
+            r#"{"a": [1, {"b": null}], "c": "d"}"#,
+        ] {
+            assert!(is_valid(input), "{input}");
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for input in [
+            "",
+            "nul",
+            "01",
+            "1.",
+            "\"a",
+            "\"\n\"",
+            r#""\x""#,
+            "[1,]",
+            r#"{"a"}"#,
+            "{1: 2}",
+            "1 2",
+            &"[".repeat(MAX_DEPTH + 2),
+        ] {
+            assert!(!is_valid(input), "{input}");
+        }
+    }
+}
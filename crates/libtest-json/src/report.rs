@@ -0,0 +1,431 @@
+//! Fold an [`Event`] stream into a summary of the run
+
+use crate::event::CaseAttachment;
+use crate::event::CaseMessage;
+use crate::event::Extension;
+use crate::event::RunMessage;
+use crate::Elapsed;
+use crate::Event;
+use crate::MessageKind;
+use crate::OutputStream;
+use crate::RunMode;
+
+/// The outcome of a run, built up from [`Event`]s
+///
+/// ```rust
+/// # use libtest_json::event::*;
+/// # use libtest_json::report::*;
+/// let mut report = RunReport::new();
+/// report.push(DiscoverCase { name: "foo".to_owned(), mode: Default::default(), selected: true, tags: Vec::new(), elapsed_s: None }.into());
+/// report.push(CaseStart { name: "foo".to_owned(), elapsed_s: None }.into());
+/// report.push(CaseComplete { name: "foo".to_owned(), seed: None, elapsed_s: None }.into());
+/// assert_eq!(report.case("foo").unwrap().status(), CaseStatus::Passed);
+/// assert_eq!(report.totals().passed, 1);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct RunReport {
+    binary: Option<String>,
+    /// Cases by binary, then by name
+    cases:
+        std::collections::BTreeMap<Option<String>, std::collections::BTreeMap<String, CaseReport>>,
+    messages: Vec<RunMessage>,
+    extensions: Vec<Extension>,
+    shuffle_seed: Option<u64>,
+    seed: Option<u64>,
+    elapsed_s: Option<Elapsed>,
+}
+
+impl RunReport {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Attribute the cases of this report to the test `binary`
+    ///
+    /// This keeps cases apart when [merging][RunReport::merge] reports from several binaries, as
+    /// their names are only unique within a binary.
+    pub fn with_binary(mut self, binary: impl Into<String>) -> Self {
+        let binary = Some(binary.into());
+        if let Some(mut cases) = self.cases.remove(&self.binary) {
+            for case in cases.values_mut() {
+                case.binary.clone_from(&binary);
+            }
+            self.cases.insert(binary.clone(), cases);
+        }
+        self.binary = binary;
+        self
+    }
+
+    /// Update the report with the next [`Event`] in the stream
+    pub fn push(&mut self, event: Event) {
+        match event {
+            Event::DiscoverStart(_) => {}
+            Event::DiscoverCase(inner) => {
+                let case = self.case_mut(inner.name);
+                case.discovered = true;
+                case.mode = inner.mode;
+                case.selected = inner.selected;
+            }
+            Event::DiscoverComplete(inner) => {
+                self.shuffle_seed = inner.shuffle_seed;
+            }
+            Event::RunStart(inner) => {
+                self.seed = inner.seed;
+            }
+            Event::CaseStart(inner) => {
+                let case = self.case_mut(inner.name);
+                case.started = true;
+                case.start_s = inner.elapsed_s;
+            }
+            Event::CaseMessage(inner) => {
+                self.case_mut(inner.name.clone()).messages.push(inner);
+            }
+            Event::CaseOutput(inner) => {
+                self.case_mut(inner.name)
+                    .output
+                    .entry(inner.stream)
+                    .or_default()
+                    .push_str(&inner.content);
+            }
+            Event::CaseAttachment(inner) => {
+                self.case_mut(inner.name.clone()).attachments.push(inner);
+            }
+            Event::CaseComplete(inner) => {
+                let case = self.case_mut(inner.name);
+                case.completed = true;
+                case.seed = inner.seed;
+                case.complete_s = inner.elapsed_s;
+            }
+            Event::RunMessage(inner) => {
+                self.messages.push(inner);
+            }
+            Event::RunComplete(inner) => {
+                self.elapsed_s = inner.elapsed_s;
+            }
+            Event::Extension(inner) => {
+                self.extensions.push(inner);
+            }
+        }
+    }
+
+    /// Combine with the report from another run, like another test binary or a re-run of failures
+    ///
+    /// Cases are the same when they have the same [binary][RunReport::with_binary] and name.
+    /// When a case ran again, the later run replaces the earlier one.
+    /// The elapsed time is summed, as if the runs were sequential.
+    pub fn merge(&mut self, other: RunReport) {
+        for (binary, cases) in other.cases {
+            let existing = self.cases.entry(binary).or_default();
+            for (name, other) in cases {
+                match existing.entry(name) {
+                    std::collections::btree_map::Entry::Vacant(entry) => {
+                        entry.insert(other);
+                    }
+                    std::collections::btree_map::Entry::Occupied(mut entry) => {
+                        entry.get_mut().merge(other);
+                    }
+                }
+            }
+        }
+        self.messages.extend(other.messages);
+        self.extensions.extend(other.extensions);
+        self.shuffle_seed = self.shuffle_seed.or(other.shuffle_seed);
+        self.seed = self.seed.or(other.seed);
+        self.elapsed_s = match (self.elapsed_s, other.elapsed_s) {
+            (Some(lhs), Some(rhs)) => Some(Elapsed(lhs.0 + rhs.0)),
+            (lhs, rhs) => lhs.or(rhs),
+        };
+    }
+
+    /// Look up a case by [name][CaseReport::name]
+    ///
+    /// For reports [merged][RunReport::merge] from several binaries, see [`RunReport::binary_case`].
+    pub fn case(&self, name: &str) -> Option<&CaseReport> {
+        self.cases.get(&self.binary)?.get(name)
+    }
+
+    /// Look up a case by its [binary][CaseReport::binary] and [name][CaseReport::name]
+    pub fn binary_case(&self, binary: Option<&str>, name: &str) -> Option<&CaseReport> {
+        self.cases
+            .iter()
+            .find(|(b, _)| b.as_deref() == binary)
+            .and_then(|(_, cases)| cases.get(name))
+    }
+
+    /// All cases seen, sorted by binary and then name
+    pub fn cases(&self) -> impl Iterator<Item = &CaseReport> {
+        self.cases.values().flat_map(|cases| cases.values())
+    }
+
+    /// All messages reported for the run as a whole, in the order received
+    pub fn messages(&self) -> &[RunMessage] {
+        &self.messages
+    }
+
+    /// Whether the run failed outside of any case, see [`RunReport::messages`]
+    pub fn run_failed(&self) -> bool {
+        self.messages
+            .iter()
+            .any(|m| matches!(m.kind, MessageKind::Fixture | MessageKind::Error))
+    }
+
+    /// All [`Extension`]s reported, in the order received
+    ///
+    /// These are not associated with [`CaseReport`]s as they may be for cases that were never
+    /// discovered or run.
+    pub fn extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+
+    /// Seed the cases were shuffled with, if they were
+    ///
+    /// For merged reports, this is from the first run.
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.shuffle_seed
+    }
+
+    /// Seed that each case's randomness was derived from
+    ///
+    /// For merged reports, this is from the first run.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Time elapsed when the run completed
+    pub fn elapsed_s(&self) -> Option<Elapsed> {
+        self.elapsed_s
+    }
+
+    pub fn totals(&self) -> Totals {
+        let mut totals = Totals::default();
+        for case in self.cases() {
+            if case.discovered && case.selected {
+                totals.selected += 1;
+            }
+            match case.status() {
+                CaseStatus::Passed => totals.passed += 1,
+                CaseStatus::Failed => totals.failed += 1,
+                CaseStatus::Ignored => totals.ignored += 1,
+                CaseStatus::NotRun => totals.not_run += 1,
+                CaseStatus::FilteredOut => totals.filtered_out += 1,
+            }
+        }
+        totals
+    }
+
+    fn case_mut(&mut self, name: String) -> &mut CaseReport {
+        let binary = &self.binary;
+        self.cases
+            .entry(binary.clone())
+            .or_default()
+            .entry(name)
+            .or_insert_with_key(|name| CaseReport::new(binary.clone(), name.clone()))
+    }
+}
+
+impl Extend<Event> for RunReport {
+    fn extend<I: IntoIterator<Item = Event>>(&mut self, iter: I) {
+        for event in iter {
+            self.push(event);
+        }
+    }
+}
+
+impl FromIterator<Event> for RunReport {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> Self {
+        let mut report = Self::new();
+        report.extend(iter);
+        report
+    }
+}
+
+/// The outcome of a single case within a [`RunReport`]
+#[derive(Clone, Debug)]
+pub struct CaseReport {
+    binary: Option<String>,
+    name: String,
+    mode: RunMode,
+    discovered: bool,
+    selected: bool,
+    started: bool,
+    completed: bool,
+    start_s: Option<Elapsed>,
+    complete_s: Option<Elapsed>,
+    seed: Option<u64>,
+    messages: Vec<CaseMessage>,
+    output: std::collections::BTreeMap<OutputStream, String>,
+    attachments: Vec<CaseAttachment>,
+}
+
+impl CaseReport {
+    fn new(binary: Option<String>, name: String) -> Self {
+        Self {
+            binary,
+            name,
+            mode: Default::default(),
+            discovered: false,
+            selected: true,
+            started: false,
+            completed: false,
+            start_s: None,
+            complete_s: None,
+            seed: None,
+            messages: Vec::new(),
+            output: Default::default(),
+            attachments: Vec::new(),
+        }
+    }
+
+    /// The test binary this case is from, see [`RunReport::with_binary`]
+    pub fn binary(&self) -> Option<&str> {
+        self.binary.as_deref()
+    }
+
+    /// An identifier that is unique across the entire run
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mode(&self) -> RunMode {
+        self.mode
+    }
+
+    /// Whether selected to be run by the user
+    ///
+    /// Cases that were never discovered are assumed to be selected.
+    pub fn selected(&self) -> bool {
+        self.selected
+    }
+
+    pub fn started(&self) -> bool {
+        self.started
+    }
+
+    pub fn completed(&self) -> bool {
+        self.completed
+    }
+
+    /// Time between the case starting and completing
+    pub fn duration(&self) -> Option<std::time::Duration> {
+        let start = self.start_s?;
+        let complete = self.complete_s?;
+        Some(complete.0.saturating_sub(start.0))
+    }
+
+    /// All messages reported for this case, in the order received
+    pub fn messages(&self) -> &[CaseMessage] {
+        &self.messages
+    }
+
+    /// Seed this case derived its randomness from, if it used any
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Captured output for `stream`, if any was reported
+    pub fn output(&self, stream: OutputStream) -> Option<&str> {
+        self.output.get(&stream).map(|s| s.as_str())
+    }
+
+    /// All files attached to this case, in the order received
+    pub fn attachments(&self) -> &[CaseAttachment] {
+        &self.attachments
+    }
+
+    pub fn status(&self) -> CaseStatus {
+        if !self.ran() {
+            return if self.selected {
+                CaseStatus::NotRun
+            } else {
+                CaseStatus::FilteredOut
+            };
+        }
+        if !self.started || !self.completed {
+            // Even override `Ignored`
+            return CaseStatus::Failed;
+        }
+        match self.message_kind() {
+            Some(MessageKind::Ignored) => CaseStatus::Ignored,
+            Some(MessageKind::Fixture | MessageKind::Error) => CaseStatus::Failed,
+            None => CaseStatus::Passed,
+        }
+    }
+
+    /// The message explaining the [`CaseReport::status`], if any
+    pub fn status_message(&self) -> Option<&str> {
+        let status = self.status();
+        let kinds: &[MessageKind] = match status {
+            CaseStatus::Failed => &[MessageKind::Fixture, MessageKind::Error],
+            CaseStatus::Ignored => &[MessageKind::Ignored],
+            CaseStatus::Passed | CaseStatus::NotRun | CaseStatus::FilteredOut => {
+                return None;
+            }
+        };
+        let message = self
+            .messages
+            .iter()
+            .rev()
+            .filter(|m| kinds.contains(&m.kind))
+            .find_map(|m| m.message.as_deref());
+        if message.is_some() || status != CaseStatus::Failed {
+            message
+        } else if !self.completed {
+            Some("test never completed")
+        } else if !self.started {
+            Some("test found that never started")
+        } else {
+            None
+        }
+    }
+
+    fn message_kind(&self) -> Option<MessageKind> {
+        // Highest precedence wins
+        self.messages.iter().map(|m| m.kind).max()
+    }
+
+    /// Whether anything was reported for the case beyond discovering it
+    fn ran(&self) -> bool {
+        self.started || self.completed || !self.messages.is_empty() || !self.output.is_empty()
+    }
+
+    fn merge(&mut self, other: CaseReport) {
+        if other.ran() {
+            // The last run wins, like when re-running failures
+            let discovered = std::mem::replace(self, other);
+            if !self.discovered {
+                self.discovered = discovered.discovered;
+                self.mode = discovered.mode;
+                self.selected = discovered.selected;
+            }
+        } else if other.discovered && !self.discovered {
+            self.discovered = true;
+            self.mode = other.mode;
+            self.selected = other.selected;
+        }
+    }
+}
+
+/// See [`CaseReport::status`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CaseStatus {
+    Passed,
+    Failed,
+    Ignored,
+    /// Selected but was never started, e.g. due to `--fail-fast`
+    NotRun,
+    /// Not selected to be run by the user
+    FilteredOut,
+}
+
+/// Number of cases in each [`CaseStatus`], see [`RunReport::totals`]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Totals {
+    /// Number of cases discovered that were selected to run
+    pub selected: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub not_run: usize,
+    pub filtered_out: usize,
+}
@@ -0,0 +1,307 @@
+use libtest_json::event::CaseComplete;
+use libtest_json::event::CaseMessage;
+use libtest_json::event::CaseOutput;
+use libtest_json::event::CaseStart;
+use libtest_json::event::DiscoverCase;
+use libtest_json::event::RunComplete;
+use libtest_json::event::RunMessage;
+use libtest_json::event::RunStart;
+use libtest_json::report::CaseStatus;
+use libtest_json::Elapsed;
+use libtest_json::Event;
+use libtest_json::MessageKind;
+use libtest_json::OutputStream;
+use libtest_json::RunReport;
+
+fn elapsed(secs: u64) -> Option<Elapsed> {
+    Some(Elapsed(std::time::Duration::from_secs(secs)))
+}
+
+fn discover(name: &str, selected: bool) -> Event {
+    DiscoverCase {
+        name: name.to_owned(),
+        mode: Default::default(),
+        selected,
+        tags: Vec::new(),
+        elapsed_s: None,
+    }
+    .into()
+}
+
+fn start(name: &str, secs: u64) -> Event {
+    CaseStart {
+        name: name.to_owned(),
+        elapsed_s: elapsed(secs),
+    }
+    .into()
+}
+
+fn message(name: &str, kind: MessageKind, message: &str) -> Event {
+    CaseMessage {
+        name: name.to_owned(),
+        kind,
+        message: Some(message.to_owned()),
+        elapsed_s: None,
+    }
+    .into()
+}
+
+fn complete(name: &str, secs: u64) -> Event {
+    CaseComplete {
+        name: name.to_owned(),
+        seed: None,
+        elapsed_s: elapsed(secs),
+    }
+    .into()
+}
+
+fn run_complete(secs: u64) -> Event {
+    RunComplete {
+        elapsed_s: elapsed(secs),
+    }
+    .into()
+}
+
+#[test]
+fn status() {
+    let report = [
+        discover("passed", true),
+        discover("failed", true),
+        discover("fixture", true),
+        discover("ignored", true),
+        discover("ignored_failed", true),
+        discover("not_run", true),
+        discover("filtered_out", false),
+        discover("never_completed", true),
+        start("passed", 1),
+        complete("passed", 3),
+        start("failed", 3),
+        message("failed", MessageKind::Error, "first"),
+        message("failed", MessageKind::Error, "second"),
+        complete("failed", 4),
+        start("fixture", 4),
+        message("fixture", MessageKind::Fixture, "no database"),
+        complete("fixture", 4),
+        start("ignored", 4),
+        message("ignored", MessageKind::Ignored, "slow"),
+        complete("ignored", 4),
+        start("ignored_failed", 4),
+        message("ignored_failed", MessageKind::Error, "broke"),
+        message("ignored_failed", MessageKind::Ignored, "flaky"),
+        complete("ignored_failed", 4),
+        start("never_completed", 5),
+        message("never_started", MessageKind::Ignored, "oops"),
+        run_complete(6),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+
+    let status = report
+        .cases()
+        .map(|c| (c.name(), c.status(), c.status_message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        status,
+        [
+            ("failed", CaseStatus::Failed, Some("second")),
+            ("filtered_out", CaseStatus::FilteredOut, None),
+            ("fixture", CaseStatus::Failed, Some("no database")),
+            ("ignored", CaseStatus::Ignored, Some("slow")),
+            ("ignored_failed", CaseStatus::Ignored, Some("flaky")),
+            (
+                "never_completed",
+                CaseStatus::Failed,
+                Some("test never completed")
+            ),
+            (
+                "never_started",
+                CaseStatus::Failed,
+                Some("test never completed")
+            ),
+            ("not_run", CaseStatus::NotRun, None),
+            ("passed", CaseStatus::Passed, None),
+        ]
+    );
+
+    let totals = report.totals();
+    assert_eq!(totals.selected, 7);
+    assert_eq!(totals.passed, 1);
+    assert_eq!(totals.failed, 4);
+    assert_eq!(totals.ignored, 2);
+    assert_eq!(totals.not_run, 1);
+    assert_eq!(totals.filtered_out, 1);
+
+    assert_eq!(
+        report.case("passed").unwrap().duration(),
+        Some(std::time::Duration::from_secs(2))
+    );
+    assert_eq!(report.case("never_completed").unwrap().duration(), None);
+    assert_eq!(report.elapsed_s(), elapsed(6));
+}
+
+#[test]
+fn output() {
+    let report = [
+        start("case", 0),
+        CaseOutput {
+            name: "case".to_owned(),
+            stream: OutputStream::Stdout,
+            content: "hello ".to_owned(),
+            elapsed_s: None,
+        }
+        .into(),
+        CaseOutput {
+            name: "case".to_owned(),
+            stream: OutputStream::Stdout,
+            content: "world".to_owned(),
+            elapsed_s: None,
+        }
+        .into(),
+        complete("case", 0),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+
+    let case = report.case("case").unwrap();
+    assert_eq!(case.output(OutputStream::Stdout), Some("hello world"));
+    assert_eq!(case.output(OutputStream::Stderr), None);
+}
+
+#[test]
+fn merge() {
+    let mut report = RunReport::new().with_binary("one");
+    report.extend([
+        discover("shared", true),
+        discover("passed", true),
+        start("shared", 0),
+        complete("shared", 1),
+        start("passed", 0),
+        complete("passed", 1),
+        run_complete(1),
+    ]);
+    let mut other = RunReport::new().with_binary("two");
+    other.extend([
+        discover("shared", true),
+        discover("filtered_out", false),
+        start("shared", 0),
+        message("shared", MessageKind::Error, "oops"),
+        complete("shared", 1),
+        run_complete(2),
+    ]);
+    report.merge(other);
+
+    let totals = report.totals();
+    assert_eq!(totals.selected, 3);
+    assert_eq!(totals.passed, 2);
+    assert_eq!(totals.failed, 1);
+    assert_eq!(totals.filtered_out, 1);
+    assert_eq!(report.elapsed_s(), elapsed(3));
+    assert_eq!(
+        report.binary_case(Some("one"), "shared").unwrap().status(),
+        CaseStatus::Passed
+    );
+    assert_eq!(
+        report.binary_case(Some("two"), "shared").unwrap().status(),
+        CaseStatus::Failed
+    );
+    assert_eq!(report.case("shared").unwrap().binary(), Some("one"));
+    assert_eq!(
+        report.cases().map(|c| c.name()).collect::<Vec<_>>(),
+        ["passed", "shared", "filtered_out", "shared"]
+    );
+}
+
+#[test]
+fn merge_rerun() {
+    let mut report = [
+        discover("flaky", true),
+        discover("passed", true),
+        start("flaky", 0),
+        message("flaky", MessageKind::Error, "oops"),
+        complete("flaky", 1),
+        start("passed", 1),
+        complete("passed", 2),
+        run_complete(2),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+    let rerun = [
+        discover("flaky", true),
+        discover("passed", false),
+        start("flaky", 0),
+        complete("flaky", 1),
+        run_complete(1),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+    report.merge(rerun);
+
+    let flaky = report.case("flaky").unwrap();
+    assert_eq!(flaky.status(), CaseStatus::Passed);
+    assert_eq!(flaky.messages().len(), 0);
+    assert_eq!(report.case("passed").unwrap().status(), CaseStatus::Passed);
+    assert_eq!(report.totals().passed, 2);
+    assert_eq!(report.elapsed_s(), elapsed(3));
+}
+
+#[test]
+fn run_message() {
+    let run_message = |kind| -> Event {
+        RunMessage {
+            kind,
+            message: Some("server failed to start".to_owned()),
+            elapsed_s: None,
+        }
+        .into()
+    };
+
+    let report = [
+        discover("passed", true),
+        start("passed", 0),
+        complete("passed", 1),
+        run_message(MessageKind::Ignored),
+        run_complete(1),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+    assert_eq!(report.messages().len(), 1);
+    assert!(!report.run_failed());
+
+    let mut report = report;
+    report.merge(
+        [run_message(MessageKind::Fixture), run_complete(1)]
+            .into_iter()
+            .collect::<RunReport>(),
+    );
+    assert_eq!(report.messages().len(), 2);
+    assert!(report.run_failed());
+    assert_eq!(report.totals().failed, 0);
+}
+
+#[test]
+fn seed() {
+    let report = [
+        RunStart {
+            seed: Some(42),
+            elapsed_s: None,
+        }
+        .into(),
+        discover("seeded", true),
+        discover("unseeded", true),
+        start("seeded", 0),
+        CaseComplete {
+            name: "seeded".to_owned(),
+            seed: Some(7),
+            elapsed_s: elapsed(1),
+        }
+        .into(),
+        start("unseeded", 0),
+        complete("unseeded", 1),
+        run_complete(1),
+    ]
+    .into_iter()
+    .collect::<RunReport>();
+    assert_eq!(report.seed(), Some(42));
+    assert_eq!(report.case("seeded").unwrap().seed(), Some(7));
+    assert_eq!(report.case("unseeded").unwrap().seed(), None);
+}
@@ -1,6 +1,7 @@
 #![cfg(feature = "serde")]
 #![cfg(feature = "json")]
 
+use libtest_json::event::Payload;
 use snapbox::prelude::*;
 use snapbox::str;
 
@@ -36,6 +37,7 @@ fn discover_case() {
             name: "Hello\tworld!".to_owned(),
             mode: libtest_json::RunMode::Test,
             selected: true,
+            tags: vec![],
             elapsed_s: None,
         },
         str![[r#"{"event":"discover_case","name":"Hello\tworld!"}"#]],
@@ -46,10 +48,11 @@ fn discover_case() {
             name: "Hello\tworld!".to_owned(),
             mode: libtest_json::RunMode::Bench,
             selected: false,
+            tags: vec!["network".to_owned(), "slow".to_owned()],
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
         str![[
-            r#"{"event":"discover_case","name":"Hello\tworld!","mode":"bench","selected":false,"elapsed_s":"0"}"#
+            r#"{"event":"discover_case","name":"Hello\tworld!","mode":"bench","selected":false,"tags":["network","slow"],"elapsed_s":"0"}"#
         ]],
     );
 }
@@ -57,29 +60,37 @@ fn discover_case() {
 #[test]
 fn discover_complete() {
     t(
-        libtest_json::event::DiscoverComplete { elapsed_s: None },
+        libtest_json::event::DiscoverComplete {
+            shuffle_seed: None,
+            elapsed_s: None,
+        },
         str![[r#"{"event":"discover_complete"}"#]],
     );
 
     t(
         libtest_json::event::DiscoverComplete {
+            shuffle_seed: Some(42),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"discover_complete","elapsed_s":"0"}"#]],
+        str![[r#"{"event":"discover_complete","shuffle_seed":42,"elapsed_s":"0"}"#]],
     );
 }
 
 #[test]
 fn suite_start() {
     t(
-        libtest_json::event::RunStart { elapsed_s: None },
+        libtest_json::event::RunStart {
+            seed: None,
+            elapsed_s: None,
+        },
         str![[r#"{"event":"run_start"}"#]],
     );
     t(
         libtest_json::event::RunStart {
+            seed: Some(42),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"run_start","elapsed_s":"0"}"#]],
+        str![[r#"{"event":"run_start","seed":42,"elapsed_s":"0"}"#]],
     );
 }
 
@@ -126,11 +137,66 @@ fn case_message() {
     );
 }
 
+#[test]
+fn case_output() {
+    t(
+        libtest_json::event::CaseOutput {
+            name: "Hello\tworld!".to_owned(),
+            stream: libtest_json::OutputStream::Stdout,
+            content: "".to_owned(),
+            elapsed_s: None,
+        },
+        str![[r#"{"event":"case_output","name":"Hello\tworld!","stream":"stdout","content":""}"#]],
+    );
+
+    t(
+        libtest_json::event::CaseOutput {
+            name: "Hello\tworld!".to_owned(),
+            stream: libtest_json::OutputStream::Stderr,
+            content: "line\n".to_owned(),
+            elapsed_s: Some(libtest_json::Elapsed(Default::default())),
+        },
+        str![[
+            r#"{"event":"case_output","name":"Hello\tworld!","stream":"stderr","content":"line\n","elapsed_s":"0"}"#
+        ]],
+    );
+}
+
+#[test]
+fn case_attachment() {
+    t(
+        libtest_json::event::CaseAttachment {
+            name: "Hello\tworld!".to_owned(),
+            attachment: "screenshot.png".to_owned(),
+            path: "/tmp/screenshot.png".to_owned(),
+            media_type: "image/png".to_owned(),
+            elapsed_s: None,
+        },
+        str![[
+            r#"{"event":"case_attachment","name":"Hello\tworld!","attachment":"screenshot.png","path":"/tmp/screenshot.png","media_type":"image/png"}"#
+        ]],
+    );
+
+    t(
+        libtest_json::event::CaseAttachment {
+            name: "Hello\tworld!".to_owned(),
+            attachment: "log".to_owned(),
+            path: "C:\\log.txt".to_owned(),
+            media_type: "text/plain".to_owned(),
+            elapsed_s: Some(libtest_json::Elapsed(Default::default())),
+        },
+        str![[
+            r#"{"event":"case_attachment","name":"Hello\tworld!","attachment":"log","path":"C:\\log.txt","media_type":"text/plain","elapsed_s":"0"}"#
+        ]],
+    );
+}
+
 #[test]
 fn case_complete() {
     t(
         libtest_json::event::CaseComplete {
             name: "Hello\tworld!".to_owned(),
+            seed: None,
             elapsed_s: None,
         },
         str![[r#"{"event":"case_complete","name":"Hello\tworld!"}"#]],
@@ -139,9 +205,32 @@ fn case_complete() {
     t(
         libtest_json::event::CaseComplete {
             name: "Hello\tworld!".to_owned(),
+            seed: Some(42),
+            elapsed_s: Some(libtest_json::Elapsed(Default::default())),
+        },
+        str![[r#"{"event":"case_complete","name":"Hello\tworld!","seed":42,"elapsed_s":"0"}"#]],
+    );
+}
+
+#[test]
+fn run_message() {
+    t(
+        libtest_json::event::RunMessage {
+            kind: libtest_json::MessageKind::Fixture,
+            message: None,
+            elapsed_s: None,
+        },
+        str![[r#"{"event":"run_message","kind":"fixture"}"#]],
+    );
+    t(
+        libtest_json::event::RunMessage {
+            kind: libtest_json::MessageKind::Fixture,
+            message: Some("Hello\tworld!".to_owned()),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"case_complete","name":"Hello\tworld!","elapsed_s":"0"}"#]],
+        str![[
+            r#"{"event":"run_message","kind":"fixture","message":"Hello\tworld!","elapsed_s":"0"}"#
+        ]],
     );
 }
 
@@ -159,3 +248,44 @@ fn suite_complete() {
         str![[r#"{"event":"run_complete","elapsed_s":"0"}"#]],
     );
 }
+
+#[test]
+fn extension() {
+    t(
+        libtest_json::event::Extension {
+            extension: "my_harness.coverage".to_owned(),
+            name: None,
+            payload: None,
+            elapsed_s: None,
+        },
+        str![[r#"{"event":"extension","extension":"my_harness.coverage"}"#]],
+    );
+
+    t(
+        libtest_json::event::Extension {
+            extension: "my_harness.coverage".to_owned(),
+            name: Some("Hello\tworld!".to_owned()),
+            payload: Some(Payload::new(r#"{"lines":[1,2],"percent":50.0}"#).unwrap()),
+            elapsed_s: Some(libtest_json::Elapsed(Default::default())),
+        },
+        str![[
+            r#"{"event":"extension","extension":"my_harness.coverage","name":"Hello\tworld!","payload":{"lines":[1,2],"percent":50.0},"elapsed_s":"0"}"#
+        ]],
+    );
+
+    // Kept on one line, otherwise as written
+    let event = libtest_json::event::Extension {
+        extension: "my_harness.coverage".to_owned(),
+        name: None,
+        payload: Some(Payload::new("{\n  \"lines\": [1, 2],\r\n  \"big\": 1e400\n}\n").unwrap()),
+        elapsed_s: None,
+    };
+    snapbox::assert_data_eq!(
+        libtest_json::Event::from(event).to_jsonline(),
+        str![[r#"{"event":"extension","extension":"my_harness.coverage","payload":{   "lines": [1, 2],    "big": 1e400 }}"#]].raw()
+    );
+
+    for payload in ["", " ", "{\"lines\":", "[1] [2]"] {
+        assert!(Payload::new(payload).is_err(), "{payload:?}");
+    }
+}
@@ -14,15 +14,10 @@ fn main() -> lexarg::Result<()> {
         match arg {
             Short("h") | Long("help") => {
                 let bin = bin.to_string_lossy();
-                let options_help = libtest_lexarg::OPTIONS_HELP.trim();
-                let after_help = libtest_lexarg::AFTER_HELP.trim();
-                println!(
-                    "Usage: {bin} [OPTIONS] [FILTER]...
-
-{options_help}
-
-{after_help}"
-                );
+                let help = lexarg::Help::new(&bin)
+                    .specs(libtest_lexarg::OPTIONS)
+                    .after_help(libtest_lexarg::AFTER_HELP);
+                print!("{help}");
                 std::process::exit(0);
             }
             // All values are the same, whether escaped or not, so its a no-op
@@ -43,9 +38,8 @@ fn main() -> lexarg::Result<()> {
         let arg = test_opts.parse_next(&mut parser, arg)?;
 
         if let Some(arg) = arg {
-            return Err(lexarg::LexError::msg("unexpected argument")
-                .unexpected(arg)
-                .into());
+            let error = lexarg::LexError::msg("unexpected argument").unexpected(arg);
+            return Err(lexarg::suggest_long(error, arg, libtest_lexarg::OPTIONS).into());
         }
     }
 
@@ -14,6 +14,8 @@
 #![warn(missing_debug_implementations, elided_lifetimes_in_paths)]
 
 use lexarg::Arg;
+use lexarg::OptSpec;
+use lexarg::PossibleValue;
 use lexarg_error::LexError;
 
 /// Parsed command-line options
@@ -34,10 +36,26 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub test_threads: Option<std::num::NonZeroUsize>,
     pub skip: Vec<String>,
+    /// Only run tests with one of these tags
+    ///
+    /// Tags joined with `+` must all be present, like `network+slow`.
+    pub include_tags: Vec<String>,
+    /// Skip tests with any of these tags, taking precedence over [`TestOpts::include_tags`]
+    ///
+    /// Tags joined with `+` must all be present, like `network+slow`.
+    pub exclude_tags: Vec<String>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
     pub fail_fast: bool,
+    /// Run tests in a random order
+    pub shuffle: bool,
+    /// Seed for [`TestOpts::shuffle`], when reproducing a previous order
+    ///
+    /// Defaults to [`TestOpts::seed`].
+    pub shuffle_seed: Option<u64>,
+    /// Seed for randomized tests, when reproducing a previous run
+    pub seed: Option<u64>,
     pub allowed_unstable: Vec<String>,
 }
 
@@ -74,6 +92,97 @@ pub enum OutputFormat {
 
 pub const UNSTABLE_OPTIONS: &str = "unstable-options";
 
+/// Arguments recognized by [`TestOptsBuilder::parse_next`], for rendering with [`lexarg::Help`]
+pub const OPTIONS: &[OptSpec] = &[
+    OptSpec::positional("FILTER")
+        .multiple()
+        .help("Skip tests whose name does not match one of the filters"),
+    OptSpec::new()
+        .long("fail-fast")
+        .help("Don't start new tests after the first failure"),
+    OptSpec::new()
+        .long("skip")
+        .value("FILTER")
+        .multiple()
+        .help("Skip tests whose names contain FILTER"),
+    OptSpec::new()
+        .long("include-tag")
+        .value("TAG")
+        .multiple()
+        .help("Only run tests with TAG, or all of the tags in TAG+TAG"),
+    OptSpec::new()
+        .long("exclude-tag")
+        .value("TAG")
+        .multiple()
+        .help("Skip tests with TAG, or all of the tags in TAG+TAG"),
+    OptSpec::new()
+        .long("exact")
+        .help("Exactly match filters rather than by substring"),
+    OptSpec::new()
+        .long("ignored")
+        .help("Run only ignored tests"),
+    OptSpec::new()
+        .long("include-ignored")
+        .help("Run ignored and not ignored tests"),
+    OptSpec::new()
+        .long("test")
+        .help("Run tests and not benchmarks"),
+    OptSpec::new()
+        .long("bench")
+        .help("Run benchmarks instead of tests"),
+    OptSpec::new()
+        .long("no-capture")
+        .help("don't capture stdout/stderr of each task,\nallow printing directly"),
+    OptSpec::new()
+        .long("shuffle")
+        .help("Run tests in random order (unstable)"),
+    OptSpec::new()
+        .long("shuffle-seed")
+        .value("SEED")
+        .help("Run tests in random order, seeded with SEED (unstable)"),
+    OptSpec::new()
+        .long("seed")
+        .value("SEED")
+        .help("Seed randomized tests with SEED, like to reproduce a failure"),
+    OptSpec::new()
+        .long("show-output")
+        .help("Show captured stdout of successful tests"),
+    OptSpec::new()
+        .long("list")
+        .help("List all tests and benchmarks"),
+    OptSpec::new()
+        .long("test-threads")
+        .value("NUM")
+        .help("Number of threads used for running tests in parallel\n(default is >1)"),
+    OptSpec::new()
+        .long("format")
+        .value("FORMAT")
+        .possible(&[
+            PossibleValue::new("pretty").help("Print verbose output"),
+            PossibleValue::new("terse").help("Display one character per test"),
+            PossibleValue::new("json").help("Output a json document"),
+        ])
+        .help("Configure formatting of output:"),
+    OptSpec::new()
+        .short("q")
+        .long("quiet")
+        .help("Display one character per test instead of one line\n(alias to --format=terse)"),
+    OptSpec::new()
+        .long("color")
+        .value("WHEN")
+        .possible(&[
+            PossibleValue::new("auto").help("detect terminal support (default)"),
+            PossibleValue::new("always").help("always colorize output"),
+            PossibleValue::new("never").help("never colorize output"),
+        ])
+        .help("Configure coloring of output:"),
+    OptSpec::new()
+        .short("Z")
+        .value("FLAG")
+        .help("Enable nightly-only flags:\n- unstable-options: Allow use of experimental features"),
+];
+
+#[deprecated(since = "0.0.3", note = "render `OPTIONS` with `lexarg::Help` instead")]
 pub const OPTIONS_HELP: &str = r#"
 Arguments:
       [FILTER]...     Skip tests whose name does not match one of the filters
@@ -82,14 +191,24 @@ Options:
       --fail-fast     Don't start new tests after the first failure
       --skip FILTER   Skip tests whose names contain FILTER
                       (this flag can be used multiple times)
+      --include-tag TAG
+                      Only run tests with TAG, or all of the tags in TAG+TAG
+                      (this flag can be used multiple times)
+      --exclude-tag TAG
+                      Skip tests with TAG, or all of the tags in TAG+TAG
+                      (this flag can be used multiple times)
       --exact         Exactly match filters rather than by substring
       --ignored       Run only ignored tests
-      --include-ignored 
+      --include-ignored
                       Run ignored and not ignored tests
       --test          Run tests and not benchmarks
       --bench         Run benchmarks instead of tests
       --no-capture    don't capture stdout/stderr of each task,
                       allow printing directly
+      --shuffle       Run tests in random order (unstable)
+      --shuffle-seed SEED
+                      Run tests in random order, seeded with SEED (unstable)
+      --seed SEED     Seed randomized tests with SEED, like to reproduce a failure
       --show-output   Show captured stdout of successful tests
       --list          List all tests and benchmarks
       --test-threads NUM
@@ -152,7 +271,8 @@ impl TestOptsBuilder {
             Long("list") => {
                 self.opts.list = true;
             }
-            Long("no-capture") => {
+            // `--nocapture` is the legacy spelling
+            Long("no-capture") | Long("nocapture") => {
                 self.opts.no_capture = true;
             }
             Long("test-threads") => {
@@ -166,11 +286,27 @@ impl TestOptsBuilder {
             Long("skip") => {
                 let filter = parser
                     .next_flag_value()
-                    .ok_or_missing(Value(std::ffi::OsStr::new("NAME")))
-                    .string("NAME")
+                    .ok_or_missing(Value(std::ffi::OsStr::new("FILTER")))
+                    .string("FILTER")
                     .within(arg)?;
                 self.opts.skip.push(filter.to_owned());
             }
+            Long("include-tag") => {
+                let tag = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("TAG")))
+                    .string("TAG")
+                    .within(arg)?;
+                self.opts.include_tags.push(tag.to_owned());
+            }
+            Long("exclude-tag") => {
+                let tag = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("TAG")))
+                    .string("TAG")
+                    .within(arg)?;
+                self.opts.exclude_tags.push(tag.to_owned());
+            }
             Long("exact") => {
                 self.opts.filter_exact = true;
             }
@@ -211,11 +347,30 @@ impl TestOptsBuilder {
             Long("show-output") => {
                 self.opts.show_output = true;
             }
+            Long("shuffle") => {
+                self.opts.shuffle = true;
+            }
+            Long("shuffle-seed") => {
+                let seed = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("SEED")))
+                    .parse()
+                    .within(arg)?;
+                self.opts.shuffle_seed = Some(seed);
+            }
+            Long("seed") => {
+                let seed = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("SEED")))
+                    .parse()
+                    .within(arg)?;
+                self.opts.seed = Some(seed);
+            }
             Short("Z") => {
                 let feature = parser
                     .next_flag_value()
-                    .ok_or_missing(Value(std::ffi::OsStr::new("FEATURE")))
-                    .string("FEATURE")
+                    .ok_or_missing(Value(std::ffi::OsStr::new("FLAG")))
+                    .string("FLAG")
                     .within(arg)?;
                 if !is_nightly() {
                     return Err(LexError::msg("expected nightly compiler").unexpected(arg));
@@ -245,6 +400,16 @@ impl TestOptsBuilder {
         if self.format.is_some() && !allow_unstable_options {
             return Err(LexError::msg("`--format` requires `-Zunstable-options`"));
         }
+        if self.opts.shuffle && !allow_unstable_options {
+            return Err(LexError::msg("`--shuffle` requires `-Zunstable-options`"));
+        }
+        if self.opts.shuffle_seed.is_some() && !allow_unstable_options {
+            return Err(LexError::msg(
+                "`--shuffle-seed` requires `-Zunstable-options`",
+            ));
+        }
+
+        self.opts.shuffle |= self.opts.shuffle_seed.is_some();
         if let Some(format) = self.format {
             self.opts.format = format;
         } else if self.quiet {
@@ -9,10 +9,20 @@ pub trait Case: Send + Sync + 'static {
     ///
     /// Must be unique across the entire test run.
     fn name(&self) -> &str;
+    /// Whether this is a test or a benchmark
+    ///
+    /// Benchmarks are still run with `--test`, see [`TestContext::current_mode`].
+    fn mode(&self) -> RunMode {
+        RunMode::Test
+    }
     fn kind(&self) -> TestKind;
     fn source(&self) -> Option<&Source>;
     /// This case cannot run in parallel to other cases within this binary
     fn exclusive(&self, state: &TestContext) -> bool;
+    /// Labels for selecting cases with `--include-tag` and `--exclude-tag`
+    fn tags(&self) -> &[String] {
+        &[]
+    }
 
     fn run(&self, state: &TestContext) -> Result<(), RunError>;
 }
@@ -21,6 +31,9 @@ impl Case for Box<dyn Case> {
     fn name(&self) -> &str {
         self.as_ref().name()
     }
+    fn mode(&self) -> RunMode {
+        self.as_ref().mode()
+    }
     fn kind(&self) -> TestKind {
         self.as_ref().kind()
     }
@@ -30,6 +43,9 @@ impl Case for Box<dyn Case> {
     fn exclusive(&self, state: &TestContext) -> bool {
         self.as_ref().exclusive(state)
     }
+    fn tags(&self) -> &[String] {
+        self.as_ref().tags()
+    }
 
     fn run(&self, state: &TestContext) -> Result<(), RunError> {
         self.as_ref().run(state)
@@ -40,6 +56,9 @@ impl Case for std::sync::Arc<dyn Case> {
     fn name(&self) -> &str {
         self.as_ref().name()
     }
+    fn mode(&self) -> RunMode {
+        self.as_ref().mode()
+    }
     fn kind(&self) -> TestKind {
         self.as_ref().kind()
     }
@@ -49,6 +68,9 @@ impl Case for std::sync::Arc<dyn Case> {
     fn exclusive(&self, state: &TestContext) -> bool {
         self.as_ref().exclusive(state)
     }
+    fn tags(&self) -> &[String] {
+        self.as_ref().tags()
+    }
 
     fn run(&self, state: &TestContext) -> Result<(), RunError> {
         self.as_ref().run(state)
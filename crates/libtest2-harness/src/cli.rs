@@ -1,2 +1,9 @@
+pub use lexarg::did_you_mean;
+pub use lexarg::expand_args;
+pub use lexarg::resolve_prefix;
+pub use lexarg::suggest_long;
+pub use lexarg::Help;
+pub use lexarg::OptSpec;
+pub use lexarg::PossibleValue;
 pub use lexarg_error::*;
 pub use lexarg_parser::*;
@@ -0,0 +1,266 @@
+//! Shell completion scripts, see `--generate-completions`
+//!
+//! Flags are completed from [`OptSpec`]s while test names are looked up on demand by calling
+//! the test binary with `--list`.
+
+use crate::cli::OptSpec;
+
+pub(crate) const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Value names that are completed with test names
+const FILTER: &str = "FILTER";
+
+pub(crate) fn write(
+    shell: &str,
+    bin: &str,
+    specs: &[OptSpec],
+    writer: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let ident = bin
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    let quoted = crate::notify::shell_quote(bin);
+    let script = match shell {
+        "bash" => bash(&quoted, &ident, specs),
+        "zsh" => zsh(bin, &quoted, &ident, specs),
+        "fish" => fish(&quoted, &ident, specs),
+        _ => unreachable!("`SHELLS` should prevent this"),
+    };
+    writer.write_all(script.as_bytes())
+}
+
+fn bash(bin: &str, ident: &str, specs: &[OptSpec]) -> String {
+    let mut flags = Vec::new();
+    let mut values = String::new();
+    for spec in specs.iter().filter(|s| !s.is_positional()) {
+        let names = spec
+            .get_short()
+            .map(|s| format!("-{s}"))
+            .into_iter()
+            .chain(spec.get_long().map(|l| format!("--{l}")))
+            .collect::<Vec<_>>();
+        flags.extend(names.iter().cloned());
+        if spec.get_value().is_none() {
+            continue;
+        }
+        let reply = if spec.get_value() == Some(FILTER) {
+            format!(r#"COMPREPLY=($(compgen -W "$(_{ident}_tests)" -- "$cur"))"#)
+        } else if !spec.get_possible().is_empty() {
+            let possible = possible(spec).join(" ");
+            format!(r#"COMPREPLY=($(compgen -W "{possible}" -- "$cur"))"#)
+        } else {
+            "COMPREPLY=()".to_owned()
+        };
+        let names = names.join("|");
+        values.push_str(&format!(
+            "        {names})
+            {reply}
+            return 0
+            ;;
+"
+        ));
+    }
+    let flags = flags.join(" ");
+
+    format!(
+        r#"_{ident}_tests() {{
+    "${{COMP_WORDS[0]}}" --list 2>/dev/null | sed -n 's/: [a-z]*$//p'
+}}
+
+_{ident}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    # `COMP_WORDBREAKS` splits `--flag=value` into `--flag`, `=`, and `value`
+    if [[ "$cur" == "=" ]]; then
+        cur=""
+    elif [[ "$prev" == "=" ]]; then
+        prev="${{COMP_WORDS[COMP_CWORD-2]}}"
+    fi
+    case "$prev" in
+{values}    esac
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -W "$(_{ident}_tests)" -- "$cur"))
+    fi
+    return 0
+}}
+
+complete -F _{ident} {bin}
+"#
+    )
+}
+
+/// `quoted` is `bin` quoted for the shell
+fn zsh(bin: &str, quoted: &str, ident: &str, specs: &[OptSpec]) -> String {
+    let mut args = String::new();
+    for spec in specs {
+        let action = if spec.get_value() == Some(FILTER) {
+            format!("_{ident}_tests")
+        } else if !spec.get_possible().is_empty() {
+            format!("({})", possible(spec).join(" "))
+        } else {
+            String::new()
+        };
+        let value = spec.get_value().unwrap_or_default();
+        if spec.is_positional() {
+            let repeat = if spec.is_multiple() { "*" } else { "" };
+            args.push_str(&format!("        '{repeat}:{value}:{action}' \\\n"));
+            continue;
+        }
+
+        let help = zsh_escape(summary(spec));
+        let repeat = if spec.is_multiple() { "*" } else { "" };
+        let names = spec
+            .get_short()
+            .map(|s| (format!("-{s}"), "+"))
+            .into_iter()
+            .chain(spec.get_long().map(|l| (format!("--{l}"), "=")))
+            .collect::<Vec<_>>();
+        let exclusive = if 1 < names.len() && !spec.is_multiple() {
+            let names = names
+                .iter()
+                .map(|(n, _)| n.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("({names})")
+        } else {
+            String::new()
+        };
+        for (name, separator) in names {
+            let separator = if spec.get_value().is_some() {
+                separator
+            } else {
+                ""
+            };
+            let value = if spec.get_value().is_some() {
+                format!(":{value}:{action}")
+            } else {
+                String::new()
+            };
+            args.push_str(&format!(
+                "        '{exclusive}{repeat}{name}{separator}[{help}]{value}' \\\n"
+            ));
+        }
+    }
+
+    // `#compdef` is read by `compinit` which doesn't unquote, so only `compdef` can handle
+    // names that need quoting
+    let header = if bin == quoted {
+        format!("#compdef {bin}\n\n")
+    } else {
+        String::new()
+    };
+    format!(
+        r#"{header}_{ident}_tests() {{
+    local -a tests
+    tests=(${{(f)"$("${{words[1]}}" --list 2>/dev/null | sed -n 's/: [a-z]*$//p')"}})
+    compadd -a tests
+}}
+
+_{ident}() {{
+    _arguments -s \
+{args}        && return 0
+}}
+
+compdef _{ident} {quoted}
+"#
+    )
+}
+
+fn fish(bin: &str, ident: &str, specs: &[OptSpec]) -> String {
+    let mut completions = String::new();
+    for spec in specs {
+        let mut line = format!("complete -c {bin}");
+        if spec.is_positional() {
+            if spec.get_value() == Some(FILTER) {
+                line.push_str(&format!(" -f -a '(__{ident}_tests)'"));
+            } else {
+                continue;
+            }
+        } else {
+            if let Some(short) = spec.get_short() {
+                line.push_str(&format!(" -s {short}"));
+            }
+            if let Some(long) = spec.get_long() {
+                line.push_str(&format!(" -l {long}"));
+            }
+            if spec.get_value().is_some() {
+                line.push_str(" -x");
+            }
+            if spec.get_value() == Some(FILTER) {
+                line.push_str(&format!(" -a '(__{ident}_tests)'"));
+            } else if !spec.get_possible().is_empty() {
+                line.push_str(&format!(" -a '{}'", possible(spec).join(" ")));
+            }
+            let help = summary(spec).replace('\\', "\\\\").replace('\'', "\\'");
+            line.push_str(&format!(" -d '{help}'"));
+        }
+        completions.push_str(&line);
+        completions.push('\n');
+    }
+
+    format!(
+        r#"function __{ident}_tests
+    set -l cmd (commandline -opc)
+    $cmd[1] --list 2>/dev/null | string replace -rf ': [a-z]+$' ''
+end
+
+{completions}"#
+    )
+}
+
+fn possible(spec: &OptSpec) -> Vec<&'static str> {
+    spec.get_possible().iter().map(|p| p.get_name()).collect()
+}
+
+/// First line of the help, without trailing punctuation from continuing onto the next line
+fn summary(spec: &OptSpec) -> &'static str {
+    let help = spec.get_help().lines().next().unwrap_or_default();
+    help.trim_end_matches([':', ','])
+}
+
+fn zsh_escape(help: &str) -> String {
+    help.replace('\'', r"'\''")
+        .replace('[', r"\[")
+        .replace(']', r"\]")
+        .replace(':', r"\:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bin_is_quoted() {
+        let bin = "my tests; rm -rf ~";
+        for (shell, expected) in [
+            (
+                "bash",
+                "complete -F _my_tests__rm__rf__ 'my tests; rm -rf ~'\n",
+            ),
+            ("zsh", "compdef _my_tests__rm__rf__ 'my tests; rm -rf ~'\n"),
+            ("fish", "complete -c 'my tests; rm -rf ~'"),
+        ] {
+            let mut script = Vec::new();
+            write(shell, bin, libtest_lexarg::OPTIONS, &mut script).unwrap();
+            let script = String::from_utf8(script).unwrap();
+            assert!(script.contains(expected), "{shell}:\n{script}");
+        }
+    }
+
+    #[test]
+    fn zsh_compdef_needs_plain_bin() {
+        for (bin, has_header) in [("tests", true), ("my tests", false)] {
+            let mut script = Vec::new();
+            write("zsh", bin, libtest_lexarg::OPTIONS, &mut script).unwrap();
+            let script = String::from_utf8(script).unwrap();
+            assert_eq!(
+                script.starts_with("#compdef "),
+                has_header,
+                "{bin}:\n{script}"
+            );
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Project defaults from `.config/libtest2.toml`, see `--no-config`
+//!
+//! Only a subset of TOML is supported, to avoid dependencies:
+//! - `key = value` pairs, one per line, without tables
+//! - Values are `true`/`false`, integers, `"basic"` or `'literal'` strings, or arrays of those
+//! - `#` comments
+//!
+//! Keys are long flags, like `test-threads = 4` or `skip = ["slow"]`.  A key is skipped when the
+//! command-line passes the same flag, or one setting the same option (see [`RELATED`]), so:
+//! - Lists, like `skip`, are replaced rather than extended
+//! - `include-ignored = true` is replaced by `--ignored`, `quiet = true` by `--format`
+//!
+//! Environment variables like `RUST_TEST_THREADS` also take precedence, see
+//! [`Harness::parse`][crate::Harness::parse].  Pass `--no-config` to ignore the config entirely.
+
+use crate::cli;
+
+pub(crate) const PATH: &str = ".config/libtest2.toml";
+
+/// Flags setting the same option, so passing one on the command-line replaces the others
+const RELATED: &[&[&str]] = &[
+    &["include-ignored", "ignored"],
+    &["test", "bench"],
+    &["quiet", "format"],
+];
+
+/// Find the closest config, looking upward from `dir`
+pub(crate) fn discover(dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(PATH))
+        .find(|path| path.is_file())
+}
+
+/// Apply the config at `path` as if its flags were passed on the command-line
+///
+/// `given` are the long flags the command-line or environment set, replacing those keys.
+pub(crate) fn apply(
+    path: &std::path::Path,
+    specs: &[cli::OptSpec],
+    given: &[&str],
+    test_opts: &mut libtest_lexarg::TestOptsBuilder,
+) -> Result<(), cli::LexError<'static>> {
+    let error = |line: usize, msg: &dyn std::fmt::Display| {
+        cli::LexError::msg(format_args!("{}:{line}: {msg}", path.display()))
+    };
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| cli::LexError::msg(format_args!("{}: {err}", path.display())))?;
+    let entries = parse(&content).map_err(|(line, msg)| error(line, &msg))?;
+
+    for entry in entries {
+        let Some(spec) = specs
+            .iter()
+            .find(|s| s.get_long() == Some(entry.key.as_str()))
+        else {
+            let msg = format!("unknown key `{}`", entry.key);
+            let possible = specs.iter().filter_map(|s| s.get_long());
+            let error = error(entry.line, &msg);
+            return Err(match cli::did_you_mean(&entry.key, possible) {
+                Some(suggestion) => error.suggest(suggestion),
+                None => error,
+            });
+        };
+        if is_given(&entry.key, given) {
+            continue;
+        }
+
+        let values = match (entry.value, spec.get_value()) {
+            (Value::Bool(true), None) => vec![None],
+            (Value::Bool(false), None) => vec![],
+            (_, None) => {
+                let msg = format!("`{}` expects `true` or `false`", entry.key);
+                return Err(error(entry.line, &msg));
+            }
+            (Value::Array(values), Some(_)) if spec.is_multiple() => {
+                values.into_iter().map(|v| Some(v.to_string())).collect()
+            }
+            (Value::Array(_), Some(_)) => {
+                let msg = format!("`{}` expects a single value", entry.key);
+                return Err(error(entry.line, &msg));
+            }
+            (value, Some(_)) => vec![Some(value.to_string())],
+        };
+        for value in values {
+            // Attach the value so it can't be mistaken for a flag
+            let arg = match value {
+                Some(value) => format!("--{}={value}", entry.key),
+                None => format!("--{}", entry.key),
+            };
+            let raw = [arg];
+            let mut parser = cli::Parser::new(&raw);
+            while let Some(arg) = parser.next_arg() {
+                match test_opts.parse_next(&mut parser, arg) {
+                    Ok(None) => {}
+                    Ok(Some(_)) => {
+                        let msg = format!("unsupported key `{}`", entry.key);
+                        return Err(error(entry.line, &msg));
+                    }
+                    Err(err) => return Err(error(entry.line, &err)),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn is_given(key: &str, given: &[&str]) -> bool {
+    let related = RELATED
+        .iter()
+        .find(|related| related.contains(&key))
+        .copied()
+        .unwrap_or(std::slice::from_ref(&key));
+    given.iter().any(|flag| related.contains(flag))
+}
+
+struct Entry {
+    key: String,
+    value: Value,
+    /// Line the key is on, 1-indexed
+    line: usize,
+}
+
+enum Value {
+    Bool(bool),
+    /// Kept as written, for parsing by the flag
+    Integer(String),
+    String(String),
+    Array(Vec<Value>),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => value.fmt(f),
+            Self::Integer(value) | Self::String(value) => value.fmt(f),
+            Self::Array(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    value.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parse the TOML subset, reporting the 1-indexed line of any error
+fn parse(content: &str) -> Result<Vec<Entry>, (usize, String)> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut cursor = Cursor {
+        chars: content.chars().peekable(),
+        line: 1,
+    };
+    loop {
+        cursor.skip_blank_lines();
+        let Some(c) = cursor.chars.peek().copied() else {
+            break;
+        };
+        let line = cursor.line;
+        if c == '[' {
+            return Err((line, "tables are not supported".to_owned()));
+        }
+
+        let key = cursor.word();
+        if key.is_empty() {
+            return Err((line, format!("expected a key, found `{c}`")));
+        }
+        cursor.skip_spaces();
+        if cursor.chars.next_if_eq(&'=').is_none() {
+            return Err((line, format!("expected `=` after `{key}`")));
+        }
+        cursor.skip_spaces();
+        let value = cursor.value(true)?;
+        cursor.skip_spaces();
+        cursor.skip_comment();
+        match cursor.chars.next() {
+            Some('\n') => cursor.line += 1,
+            None => {}
+            Some(c) => return Err((cursor.line, format!("expected a newline, found `{c}`"))),
+        }
+
+        if entries.iter().any(|e| e.key == key) {
+            return Err((line, format!("duplicate key `{key}`")));
+        }
+        entries.push(Entry { key, value, line });
+    }
+    Ok(entries)
+}
+
+struct Cursor<'c> {
+    chars: std::iter::Peekable<std::str::Chars<'c>>,
+    line: usize,
+}
+
+impl Cursor<'_> {
+    fn skip_spaces(&mut self) {
+        while self
+            .chars
+            .next_if(|c| matches!(c, ' ' | '\t' | '\r'))
+            .is_some()
+        {}
+    }
+
+    fn skip_comment(&mut self) {
+        if self.chars.peek() == Some(&'#') {
+            while self.chars.next_if(|c| *c != '\n').is_some() {}
+        }
+    }
+
+    /// Skip whitespace, including newlines, and comments
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            match self.chars.next_if(|c| *c == '\n' || *c == '\r') {
+                Some('\n') => self.line += 1,
+                Some(_) => {}
+                None => break,
+            }
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+'))
+        {
+            word.push(c);
+        }
+        word
+    }
+
+    fn value(&mut self, allow_array: bool) -> Result<Value, (usize, String)> {
+        let line = self.line;
+        match self.chars.peek().copied() {
+            Some('"') => {
+                self.chars.next();
+                let mut value = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match self.chars.next() {
+                            Some('"') => value.push('"'),
+                            Some('\\') => value.push('\\'),
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(c) => return Err((line, format!("unsupported escape `\\{c}`"))),
+                            None => return Err((line, "unterminated `\"`".to_owned())),
+                        },
+                        Some('\n') | None => return Err((line, "unterminated `\"`".to_owned())),
+                        Some(c) => value.push(c),
+                    }
+                }
+                Ok(Value::String(value))
+            }
+            Some('\'') => {
+                self.chars.next();
+                let mut value = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('\'') => break,
+                        Some('\n') | None => return Err((line, "unterminated `'`".to_owned())),
+                        Some(c) => value.push(c),
+                    }
+                }
+                Ok(Value::String(value))
+            }
+            Some('[') if allow_array => {
+                self.chars.next();
+                let mut values = Vec::new();
+                loop {
+                    self.skip_blank_lines();
+                    if self.chars.next_if_eq(&']').is_some() {
+                        break;
+                    }
+                    values.push(self.value(false)?);
+                    self.skip_blank_lines();
+                    if self.chars.next_if_eq(&',').is_none() {
+                        self.skip_blank_lines();
+                        if self.chars.next_if_eq(&']').is_some() {
+                            break;
+                        }
+                        return Err((self.line, "expected `,` or `]`".to_owned()));
+                    }
+                }
+                Ok(Value::Array(values))
+            }
+            _ => {
+                let word = self.word();
+                match word.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    _ => {
+                        let integer = word.replace('_', "");
+                        if integer.parse::<i64>().is_ok() {
+                            Ok(Value::Integer(integer))
+                        } else if word.is_empty() {
+                            Err((line, "expected a value".to_owned()))
+                        } else {
+                            Err((line, format!("expected a value, found `{word}`")))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(content: &str) -> Vec<(String, String, usize)> {
+        parse(content)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.key, e.value.to_string(), e.line))
+            .collect()
+    }
+
+    #[test]
+    fn values() {
+        let content = r#"
+# Defaults for the whole team
+test-threads = 4
+exact = true  # trailing comment
+fail-fast = false
+color = "always"
+format = 'terse'
+skip = [
+    "slow", # network
+    'flaky',
+]
+"#;
+        assert_eq!(
+            parse_ok(content),
+            [
+                ("test-threads".to_owned(), "4".to_owned(), 3),
+                ("exact".to_owned(), "true".to_owned(), 4),
+                ("fail-fast".to_owned(), "false".to_owned(), 5),
+                ("color".to_owned(), "always".to_owned(), 6),
+                ("format".to_owned(), "terse".to_owned(), 7),
+                ("skip".to_owned(), "slow,flaky".to_owned(), 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let cases = [
+            ("[profile]\n", (1, "tables are not supported")),
+            ("\nexact true\n", (2, "expected `=` after `exact`")),
+            ("skip = \"slow\n", (1, "unterminated `\"`")),
+            ("exact = yes\n", (1, "expected a value, found `yes`")),
+            ("skip = [\"a\" \"b\"]\n", (1, "expected `,` or `]`")),
+            ("exact = true false\n", (1, "expected a newline, found `f`")),
+            (
+                "exact = true\nexact = false\n",
+                (2, "duplicate key `exact`"),
+            ),
+        ];
+        for (content, (line, msg)) in cases {
+            let Err(actual) = parse(content) else {
+                panic!("{content:?} should fail");
+            };
+            assert_eq!(actual, (line, msg.to_owned()), "{content:?}");
+        }
+    }
+}
@@ -6,6 +6,10 @@ pub struct TestContext {
     pub(crate) run_ignored: bool,
     pub(crate) notifier: notify::ArcNotifier,
     pub(crate) test_name: String,
+    pub(crate) seed: u64,
+    pub(crate) seed_used: std::sync::atomic::AtomicBool,
+    /// Names passed to [`TestContext::attach_file`], to keep them unique
+    pub(crate) attachments: std::sync::Mutex<Vec<String>>,
 }
 
 impl TestContext {
@@ -33,6 +37,160 @@ impl TestContext {
         self.notifier().notify(event)
     }
 
+    /// Report output captured from the current test
+    ///
+    /// This is shown to the user if the test fails.
+    ///
+    /// The harness doesn't capture `print!` and friends, as stable Rust has no hook for it, so
+    /// they go straight to the process' stdout and only output passed in here is reported.
+    pub fn write_output(
+        &self,
+        stream: OutputStream,
+        content: impl Into<String>,
+    ) -> std::io::Result<()> {
+        self.notifier().notify(
+            notify::event::CaseOutput {
+                name: self.test_name.clone(),
+                stream,
+                content: content.into(),
+                elapsed_s: Some(self.elapsed_s()),
+            }
+            .into(),
+        )
+    }
+
+    /// Publish a file produced by the current test, like a snapshot or screenshot
+    ///
+    /// `path` must exist, failing with [`std::io::ErrorKind::NotFound`] otherwise.
+    ///
+    /// `name` must be unique within the test, failing with [`std::io::ErrorKind::AlreadyExists`]
+    /// otherwise.
+    pub fn attach_file(
+        &self,
+        name: impl Into<String>,
+        path: impl AsRef<std::path::Path>,
+        media_type: &str,
+    ) -> std::io::Result<()> {
+        let name = name.into();
+        let path = path.as_ref();
+        let path = if path.is_absolute() {
+            path.to_owned()
+        } else {
+            std::env::current_dir()?.join(path)
+        };
+        if !path.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("`{}` does not exist", path.display()),
+            ));
+        }
+        self.claim_attachment(&name)?;
+        self.notify_attachment(name, &path, media_type)
+    }
+
+    /// Publish content produced by the current test, like a snapshot or screenshot
+    ///
+    /// The content is written to a temporary directory that outlives the test run.  Directories
+    /// left by runs from more than a day ago are removed.
+    ///
+    /// `name` must be unique within the test and may not be empty, `.`, or `..`, failing with
+    /// [`std::io::ErrorKind::AlreadyExists`] or [`std::io::ErrorKind::InvalidInput`] otherwise.
+    pub fn attach_bytes(
+        &self,
+        name: impl Into<String>,
+        content: impl AsRef<[u8]>,
+        media_type: &str,
+    ) -> std::io::Result<()> {
+        let name = name.into();
+        if matches!(name.as_str(), "" | "." | "..") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("`{name}` is not a valid attachment name"),
+            ));
+        }
+        self.claim_attachment(&name)?;
+        let dir = attachments_dir().join(encode_file_name(&self.test_name));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(encode_file_name(&name));
+        std::fs::write(&path, content)?;
+        self.notify_attachment(name, &path, media_type)
+    }
+
+    fn claim_attachment(&self, name: &str) -> std::io::Result<()> {
+        let mut attachments = self
+            .attachments
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if attachments.iter().any(|a| a == name) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("`{name}` is already attached"),
+            ));
+        }
+        attachments.push(name.to_owned());
+        Ok(())
+    }
+
+    fn notify_attachment(
+        &self,
+        name: String,
+        path: &std::path::Path,
+        media_type: &str,
+    ) -> std::io::Result<()> {
+        self.notifier().notify(
+            notify::event::CaseAttachment {
+                name: self.test_name.clone(),
+                attachment: name,
+                path: path.to_string_lossy().into_owned(),
+                media_type: media_type.to_owned(),
+                elapsed_s: Some(self.elapsed_s()),
+            }
+            .into(),
+        )
+    }
+
+    /// Report custom data for the current test
+    ///
+    /// `extension` should be namespaced, e.g. `my_harness.coverage`.
+    /// `payload` must be valid JSON, failing with [`std::io::ErrorKind::InvalidInput`] otherwise.
+    ///
+    /// This is only shown with `--format json`.
+    pub fn notify_extension(
+        &self,
+        extension: impl Into<String>,
+        payload: impl Into<String>,
+    ) -> std::io::Result<()> {
+        let extension = extension.into();
+        let payload = notify::event::Payload::new(payload).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("`{extension}` payload is not valid JSON"),
+            )
+        })?;
+        self.notify(
+            notify::event::Extension {
+                extension,
+                name: Some(self.test_name.clone()),
+                payload: Some(payload),
+                elapsed_s: Some(self.elapsed_s()),
+            }
+            .into(),
+        )
+    }
+
+    /// Report what a benchmark measured for the current test
+    ///
+    /// This is shown as `bench: <median> ns/iter (+/- <deviation>)` and, with `--format json`,
+    /// reported as a `libtest2.bench` extension event, like
+    /// `{"median_ns":1200,"deviation_ns":35}`.
+    pub fn notify_bench(&self, median_ns: u128, deviation_ns: u128) -> std::io::Result<()> {
+        let bench = notify::Bench {
+            median_ns,
+            deviation_ns,
+        };
+        self.notify_extension(notify::Bench::EXTENSION, bench.to_payload())
+    }
+
     pub fn elapsed_s(&self) -> notify::Elapsed {
         notify::Elapsed(self.start.elapsed())
     }
@@ -41,6 +199,28 @@ impl TestContext {
         &self.test_name
     }
 
+    /// A seed for randomizing the current test, see also [`TestContext::rng`]
+    ///
+    /// This is derived from the run's `--seed` and the test's name.  If the test fails after
+    /// calling this, the seed is reported with how to reproduce the failure.
+    pub fn seed(&self) -> u64 {
+        self.seed_used
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        rng::case_seed(self.seed, &self.test_name)
+    }
+
+    /// A random number generator for the current test, from [`TestContext::seed`]
+    pub fn rng(&self) -> Rng {
+        Rng::new(self.seed())
+    }
+
+    /// The [`TestContext::seed`], if the current test asked for it
+    pub(crate) fn used_seed(&self) -> Option<u64> {
+        self.seed_used
+            .load(std::sync::atomic::Ordering::Relaxed)
+            .then(|| rng::case_seed(self.seed, &self.test_name))
+    }
+
     pub(crate) fn notifier(&self) -> &notify::ArcNotifier {
         &self.notifier
     }
@@ -52,6 +232,86 @@ impl TestContext {
             run_ignored: self.run_ignored,
             notifier: self.notifier.clone(),
             test_name: self.test_name.clone(),
+            seed: self.seed,
+            seed_used: Default::default(),
+            attachments: Default::default(),
+        }
+    }
+}
+
+/// Where [`TestContext::attach_bytes`] writes, unique to this process
+///
+/// Attachments are read after the run, so rather than removing this directory when done, those
+/// left by earlier runs are removed once stale.
+fn attachments_dir() -> &'static std::path::Path {
+    static DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+    DIR.get_or_init(|| {
+        let temp_dir = std::env::temp_dir();
+        remove_stale_attachments(&temp_dir);
+        temp_dir.join(format!("{ATTACHMENTS_PREFIX}{}", std::process::id()))
+    })
+}
+
+const ATTACHMENTS_PREFIX: &str = "libtest2-";
+
+/// How long attachments are kept for
+const ATTACHMENTS_TTL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+fn remove_stale_attachments(temp_dir: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(temp_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let is_attachments = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(ATTACHMENTS_PREFIX))
+            .is_some_and(|pid| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()));
+        let is_stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| ATTACHMENTS_TTL < age);
+        if is_attachments && is_stale {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Percent-encode `name` so distinct names map to distinct, portable file names
+///
+/// A leading `.` is encoded so the result is never `.`, `..`, or hidden.
+fn encode_file_name(name: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut encoded = String::with_capacity(name.len());
+    for (i, b) in name.bytes().enumerate() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_') || (b == b'.' && i != 0) {
+            encoded.push(b as char);
+        } else {
+            let _ = write!(encoded, "%{b:02X}");
         }
     }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_file_name_is_unique() {
+        assert_eq!(encode_file_name("a::b"), "a%3A%3Ab");
+        assert_eq!(encode_file_name("a__b"), "a__b");
+        assert_eq!(encode_file_name("a%3A%3Ab"), "a%253A%253Ab");
+        assert_eq!(encode_file_name("snapshot.txt"), "snapshot.txt");
+    }
+
+    #[test]
+    fn encode_file_name_is_not_special() {
+        assert_eq!(encode_file_name("."), "%2E");
+        assert_eq!(encode_file_name(".."), "%2E.");
+        assert_eq!(encode_file_name("../x"), "%2E.%2Fx");
+    }
 }
@@ -0,0 +1,82 @@
+//! libtest's environment variables, see [`Harness::parse`][crate::Harness::parse]
+
+use crate::cli;
+
+/// The variable for each flag
+const VARS: &[(&str, &str)] = &[
+    ("test-threads", "RUST_TEST_THREADS"),
+    ("no-capture", "RUST_TEST_NOCAPTURE"),
+    ("shuffle", "RUST_TEST_SHUFFLE"),
+    ("shuffle-seed", "RUST_TEST_SHUFFLE_SEED"),
+];
+
+/// Flags with a variable set, so the config can skip them
+pub(crate) fn given_flags() -> impl Iterator<Item = &'static str> {
+    VARS.iter()
+        .filter(|(_, var)| std::env::var_os(var).is_some())
+        .map(|(flag, _)| *flag)
+}
+
+/// Override `opts` with the environment, except for flags in `given` on the command-line
+pub(crate) fn apply(
+    given: &[&str],
+    opts: &mut libtest_lexarg::TestOpts,
+) -> Result<(), cli::LexError<'static>> {
+    if !given.contains(&"test-threads") {
+        if let Some(value) = var("RUST_TEST_THREADS")? {
+            let test_threads = value.parse().map_err(|_| {
+                cli::LexError::msg(format_args!(
+                    "`RUST_TEST_THREADS` is `{value}`, should be a positive integer"
+                ))
+            })?;
+            opts.test_threads = Some(test_threads);
+        }
+    }
+    if !given.contains(&"no-capture") {
+        if let Some(no_capture) = flag("RUST_TEST_NOCAPTURE")? {
+            opts.no_capture = no_capture;
+        }
+    }
+
+    // Like libtest, these are unstable, so only honored when the flags are allowed
+    let allow_unstable_options = opts
+        .allowed_unstable
+        .iter()
+        .any(|f| f == libtest_lexarg::UNSTABLE_OPTIONS);
+    if !allow_unstable_options {
+        return Ok(());
+    }
+    if !given.contains(&"shuffle") {
+        if let Some(shuffle) = flag("RUST_TEST_SHUFFLE")? {
+            opts.shuffle = shuffle;
+        }
+    }
+    if !given.contains(&"shuffle-seed") {
+        if let Some(value) = var("RUST_TEST_SHUFFLE_SEED")? {
+            let seed = value.parse().map_err(|_| {
+                cli::LexError::msg(format_args!(
+                    "`RUST_TEST_SHUFFLE_SEED` is `{value}`, should be a number"
+                ))
+            })?;
+            opts.shuffle_seed = Some(seed);
+            opts.shuffle = true;
+        }
+    }
+
+    Ok(())
+}
+
+fn var(name: &str) -> Result<Option<String>, cli::LexError<'static>> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => Err(cli::LexError::msg(format_args!(
+            "`{name}` is not valid UTF-8"
+        ))),
+    }
+}
+
+/// Like libtest, any value besides `0` enables the flag
+fn flag(name: &str) -> Result<Option<bool>, cli::LexError<'static>> {
+    Ok(var(name)?.map(|value| value != "0"))
+}
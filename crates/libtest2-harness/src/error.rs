@@ -20,6 +20,16 @@ impl RunError {
         Self::with_cause(Message(cause.to_string()))
     }
 
+    /// A fixture the test depends on failed to set up or tear down
+    ///
+    /// This is reported separately from the test itself failing.
+    pub fn fixture(cause: impl std::fmt::Display) -> Self {
+        Self {
+            status: notify::MessageKind::Fixture,
+            cause: Some(Box::new(Message(cause.to_string()))),
+        }
+    }
+
     /// Should not be called with `libtest_lexarg::RunIgnored::Yes`
     pub fn ignore() -> Self {
         Self {
@@ -45,6 +55,15 @@ impl RunError {
     }
 }
 
+impl std::fmt::Display for RunError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.cause() {
+            Some(cause) => std::fmt::Display::fmt(cause, formatter),
+            None => self.status.as_str().fmt(formatter),
+        }
+    }
+}
+
 impl<E> From<E> for RunError
 where
     E: std::error::Error + Send + Sync + 'static,
@@ -65,6 +84,25 @@ impl std::fmt::Display for Message {
 
 impl std::error::Error for Message {}
 
+/// Convert the return value of a test into a [`RunResult`]
+///
+/// This mirrors [`std::process::Termination`]:
+/// - `()`
+/// - [`ExitCode`][std::process::ExitCode], failing unless it is `SUCCESS`
+/// - `Result<T, E>` for any of these `T` and any `E: Debug`, like `Box<dyn Error>`, reporting
+///   the error with its `Debug` representation.  A [`RunError`] is kept as-is so ignoring a test
+///   still works.
+///
+/// `#[libtest2::test]` reports errors that convert into a [`RunError`], like
+/// [`std::io::Error`], with their `Display` representation instead.
+#[cfg_attr(
+    not(no_diagnostic_namespace),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a supported test return type",
+        label = "unsupported return type",
+        note = "tests may return `()`, `ExitCode`, or `Result<T, E>` with one of these `T` and `E: Debug`"
+    )
+)]
 pub trait IntoRunResult {
     fn into_run_result(self) -> RunResult;
 }
@@ -75,17 +113,61 @@ impl IntoRunResult for () {
     }
 }
 
-impl IntoRunResult for RunResult {
+impl IntoRunResult for std::convert::Infallible {
+    fn into_run_result(self) -> RunResult {
+        match self {}
+    }
+}
+
+impl IntoRunResult for std::process::ExitCode {
     fn into_run_result(self) -> RunResult {
-        self
+        // `ExitCode` is opaque, without `PartialEq` until Rust 1.79
+        if format!("{self:?}") == format!("{:?}", std::process::ExitCode::SUCCESS) {
+            Ok(())
+        } else {
+            Err(RunError::fail(format_args!(
+                "test returned a failing exit code: {self:?}"
+            )))
+        }
     }
 }
 
-impl<E> IntoRunResult for Result<(), E>
+impl<T, E> IntoRunResult for Result<T, E>
 where
-    E: std::error::Error + Send + Sync + 'static,
+    T: IntoRunResult,
+    E: std::fmt::Debug + 'static,
 {
     fn into_run_result(self) -> RunResult {
-        self.map_err(RunError::with_cause)
+        match self {
+            Ok(ok) => ok.into_run_result(),
+            Err(err) => {
+                let mut err = Some(err);
+                let any = &mut err as &mut dyn std::any::Any;
+                if let Some(err) = any.downcast_mut::<Option<RunError>>() {
+                    return Err(err.take().expect("only taken once"));
+                }
+                let err = err.expect("only taken by a `RunError`");
+                Err(RunError::fail(format_args!("{err:?}")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_error_is_kept() {
+        let err = Err::<(), _>(RunError::ignore())
+            .into_run_result()
+            .unwrap_err();
+        assert_eq!(err.status(), notify::MessageKind::Ignored);
+    }
+
+    #[test]
+    fn exit_code() {
+        assert!(std::process::ExitCode::SUCCESS.into_run_result().is_ok());
+        assert!(std::process::ExitCode::FAILURE.into_run_result().is_err());
     }
 }
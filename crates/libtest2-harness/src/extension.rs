@@ -0,0 +1,96 @@
+pub(crate) use crate::*;
+
+/// Custom flags, see [`Harness::parse_with`]
+pub trait ArgParser {
+    /// Flags accepted by [`ArgParser::parse_next`], for `--help`, abbreviations, and suggestions
+    fn options(&self) -> &'static [cli::OptSpec];
+
+    /// Parse `arg`, returning it if it isn't for this parser
+    ///
+    /// Values consumed before returning `arg` are given back to the parser.
+    fn parse_next<'a>(
+        &mut self,
+        parser: &mut cli::Parser<'a>,
+        arg: cli::Arg<'a>,
+    ) -> Result<Option<cli::Arg<'a>>, cli::LexError<'a>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Takes a value for every flag, keeping it only for `--level`
+    #[derive(Default)]
+    struct Level(Option<String>);
+
+    impl ArgParser for Level {
+        fn options(&self) -> &'static [cli::OptSpec] {
+            const OPTIONS: &[cli::OptSpec] = &[cli::OptSpec::new().long("level").value("LEVEL")];
+            OPTIONS
+        }
+
+        fn parse_next<'a>(
+            &mut self,
+            parser: &mut cli::Parser<'a>,
+            arg: cli::Arg<'a>,
+        ) -> Result<Option<cli::Arg<'a>>, cli::LexError<'a>> {
+            let value = parser.next_flag_value();
+            match arg {
+                cli::Arg::Long("level") => {
+                    self.0 = value.map(|v| v.to_string_lossy().into_owned());
+                    Ok(None)
+                }
+                _ => Ok(Some(arg)),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct Name(Option<String>);
+
+    impl ArgParser for Name {
+        fn options(&self) -> &'static [cli::OptSpec] {
+            const OPTIONS: &[cli::OptSpec] = &[cli::OptSpec::new().long("name").value("NAME")];
+            OPTIONS
+        }
+
+        fn parse_next<'a>(
+            &mut self,
+            parser: &mut cli::Parser<'a>,
+            arg: cli::Arg<'a>,
+        ) -> Result<Option<cli::Arg<'a>>, cli::LexError<'a>> {
+            match arg {
+                cli::Arg::Long("name") => {
+                    let value = parser.next_flag_value();
+                    self.0 = value.map(|v| v.to_string_lossy().into_owned());
+                    Ok(None)
+                }
+                _ => Ok(Some(arg)),
+            }
+        }
+    }
+
+    #[test]
+    fn dispatch() {
+        let harness = Harness::new()
+            .with_args(["test", "--no-config", "--name", "a", "--level", "1", "foo"])
+            .unwrap();
+        let mut level = Level::default();
+        let mut name = Name::default();
+        harness.parse_with(&mut [&mut level, &mut name]).unwrap();
+        assert_eq!(level.0.as_deref(), Some("1"));
+        assert_eq!(name.0.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn unknown() {
+        let harness = Harness::new()
+            .with_args(["test", "--no-config", "--nom", "a"])
+            .unwrap();
+        let mut name = Name::default();
+        let Err(err) = harness.parse_with(&mut [&mut name]) else {
+            panic!("`--nom` should be rejected");
+        };
+        assert!(err.to_string().contains("unexpected argument"), "{err}");
+    }
+}
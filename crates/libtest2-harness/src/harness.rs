@@ -1,6 +1,8 @@
 use libtest_lexarg::OutputFormat;
 
-use crate::{cli, notify, Case, RunError, RunMode, TestContext};
+use crate::{
+    cli, complete, config, env, notify, rng, ArgParser, Case, Hooks, RunError, RunMode, TestContext,
+};
 
 pub trait HarnessState: sealed::_HarnessState_is_Sealed {}
 
@@ -32,7 +34,7 @@ impl Harness<StateInitial> {
         self,
         args: impl IntoIterator<Item = impl Into<std::ffi::OsString>>,
     ) -> std::io::Result<Harness<StateArgs>> {
-        let raw = expand_args(args)?;
+        let raw = cli::expand_args(args)?;
         Ok(Harness {
             state: StateArgs {
                 start: self.state.start,
@@ -56,9 +58,46 @@ impl HarnessState for StateArgs {}
 impl sealed::_HarnessState_is_Sealed for StateArgs {}
 
 impl Harness<StateArgs> {
+    /// Parse the command-line, on top of libtest's environment variables and the defaults from
+    /// `.config/libtest2.toml`
+    ///
+    /// Precedence, from highest to lowest:
+    /// 1. The command-line
+    /// 2. The environment:
+    ///    - `RUST_TEST_THREADS` for `--test-threads`
+    ///    - `RUST_TEST_NOCAPTURE` for `--no-capture`
+    ///    - `RUST_TEST_SHUFFLE` for `--shuffle`, with `-Zunstable-options`
+    ///    - `RUST_TEST_SHUFFLE_SEED` for `--shuffle-seed`, with `-Zunstable-options`
+    /// 3. The config, which `--no-config` ignores
+    ///
+    /// A flag replaces the value of lower sources, including for related flags, like `--format`
+    /// replacing `quiet = true`.
     pub fn parse(&self) -> Result<Harness<StateParsed>, cli::LexError<'_>> {
+        self.parse_with(&mut [])
+    }
+
+    /// Like [`Harness::parse`], also accepting the flags of `extensions`
+    ///
+    /// Each argument libtest doesn't recognize is offered to `extensions` in order.
+    pub fn parse_with(
+        &self,
+        extensions: &mut [&mut dyn ArgParser],
+    ) -> Result<Harness<StateParsed>, cli::LexError<'_>> {
+        let options = options(extensions);
+        let mut test_opts = libtest_lexarg::TestOptsBuilder::new();
+        let given = given_flags(&self.state.raw, &options);
+        if !given.contains(&"no-config") {
+            let config = std::env::current_dir()
+                .ok()
+                .and_then(|dir| config::discover(&dir));
+            if let Some(config) = config {
+                let given: Vec<_> = given.iter().copied().chain(env::given_flags()).collect();
+                config::apply(&config, &options, &given, &mut test_opts)?;
+            }
+        }
+
         let mut parser = cli::Parser::new(&self.state.raw);
-        let opts = parse(&mut parser)?;
+        let opts = parse(&mut parser, &options, &given, test_opts, extensions)?;
 
         #[cfg(feature = "color")]
         match opts.color {
@@ -68,7 +107,13 @@ impl Harness<StateArgs> {
         }
         .write_global();
 
-        let notifier = notifier(&opts);
+        let bin = self
+            .state
+            .raw
+            .first()
+            .map(|bin| display_bin(std::path::Path::new(bin)))
+            .unwrap_or_else(|| "test".to_owned());
+        let notifier = notifier(&opts, bin);
 
         Ok(Harness {
             state: StateParsed {
@@ -80,6 +125,26 @@ impl Harness<StateArgs> {
     }
 }
 
+impl Harness<StateArgs> {
+    /// Render an error from [`Harness::parse`], pointing at the offending argument
+    pub fn render_error(&self, error: &cli::LexError<'_>) -> String {
+        let bin = self
+            .state
+            .raw
+            .first()
+            .map(std::path::Path::new)
+            .and_then(|bin| bin.file_name())
+            .unwrap_or(std::ffi::OsStr::new("test"))
+            .to_string_lossy();
+        let usage = cli::Help::new(&bin).specs(&options(&[])).usage();
+        error
+            .render(&self.state.raw)
+            .usage(&usage)
+            .help_flag("--help")
+            .to_string()
+    }
+}
+
 pub struct StateParsed {
     start: std::time::Instant,
     opts: libtest_lexarg::TestOpts,
@@ -106,8 +171,9 @@ impl Harness<StateParsed> {
             self.state.notifier.notify(
                 notify::event::DiscoverCase {
                     name: case.name().to_owned(),
-                    mode: RunMode::Test,
+                    mode: case.mode(),
                     selected,
+                    tags: case.tags().to_vec(),
                     elapsed_s: Some(notify::Elapsed(self.state.start.elapsed())),
                 }
                 .into(),
@@ -122,9 +188,20 @@ impl Harness<StateParsed> {
             let name = case.name().to_owned();
             (priority, name)
         });
+        let seed = self.state.opts.seed.unwrap_or_else(rng::run_seed);
+        // Derived from `seed` so `--seed` alone reproduces the order
+        let shuffle_seed = self
+            .state
+            .opts
+            .shuffle
+            .then(|| self.state.opts.shuffle_seed.unwrap_or(seed));
+        if let Some(seed) = shuffle_seed {
+            shuffle(&mut selected_cases, seed);
+        }
 
         self.state.notifier.notify(
             notify::event::DiscoverComplete {
+                shuffle_seed,
                 elapsed_s: Some(notify::Elapsed(self.state.start.elapsed())),
             }
             .into(),
@@ -136,6 +213,7 @@ impl Harness<StateParsed> {
                 opts: self.state.opts,
                 notifier: self.state.notifier,
                 cases: selected_cases,
+                seed,
             },
         })
     }
@@ -146,12 +224,18 @@ pub struct StateDiscovered {
     opts: libtest_lexarg::TestOpts,
     notifier: notify::ArcNotifier,
     cases: Vec<Box<dyn Case>>,
+    seed: u64,
 }
 impl HarnessState for StateDiscovered {}
 impl sealed::_HarnessState_is_Sealed for StateDiscovered {}
 
 impl Harness<StateDiscovered> {
     pub fn run(self) -> std::io::Result<bool> {
+        self.run_with_hooks(())
+    }
+
+    /// Run the cases, calling `hooks` around them
+    pub fn run_with_hooks(self, hooks: impl Hooks) -> std::io::Result<bool> {
         if self.state.opts.list {
             Ok(true)
         } else {
@@ -159,7 +243,9 @@ impl Harness<StateDiscovered> {
                 &self.state.start,
                 &self.state.opts,
                 self.state.cases,
+                self.state.seed,
                 self.state.notifier,
+                std::sync::Arc::new(hooks),
             )
         }
     }
@@ -173,55 +259,109 @@ mod sealed {
 
 pub const ERROR_EXIT_CODE: i32 = 101;
 
-fn expand_args(
-    args: impl IntoIterator<Item = impl Into<std::ffi::OsString>>,
-) -> std::io::Result<Vec<std::ffi::OsString>> {
-    let mut expanded = Vec::new();
-    for arg in args {
-        let arg = arg.into();
-        if let Some(argfile) = arg.to_str().and_then(|s| s.strip_prefix("@")) {
-            expanded.extend(parse_argfile(std::path::Path::new(argfile))?);
-        } else {
-            expanded.push(arg);
+/// Arguments handled by the harness, in addition to [`libtest_lexarg::OPTIONS`]
+const OPTIONS: &[cli::OptSpec] = &[cli::OptSpec::new()
+    .long("no-config")
+    .help("Don't load defaults from `.config/libtest2.toml`")];
+
+fn options(extensions: &[&mut dyn ArgParser]) -> Vec<cli::OptSpec> {
+    libtest_lexarg::OPTIONS
+        .iter()
+        .chain(OPTIONS)
+        .chain(extensions.iter().flat_map(|e| e.options()))
+        .copied()
+        .collect()
+}
+
+/// Long names of the flags passed on the command-line
+///
+/// This is checked before parsing so the config can be applied first, skipping what the
+/// command-line replaces.  Errors are left for [`parse`] to report.
+fn given_flags(raw: &[std::ffi::OsString], options: &[cli::OptSpec]) -> Vec<&'static str> {
+    let mut given = Vec::new();
+    let mut parser = cli::Parser::new(&raw);
+    while let Some(arg) = parser.next_arg() {
+        let spec = match resolve_prefix(arg, options) {
+            // `--nocapture` is the legacy spelling of `--no-capture`
+            Ok(cli::Arg::Long("nocapture")) => {
+                options.iter().find(|s| s.get_long() == Some("no-capture"))
+            }
+            Ok(cli::Arg::Long(long)) => options.iter().find(|s| s.get_long() == Some(long)),
+            Ok(cli::Arg::Short(short)) => options.iter().find(|s| s.get_short() == Some(short)),
+            Ok(cli::Arg::Escape(_)) => break,
+            _ => None,
+        };
+        let Some(spec) = spec else {
+            continue;
+        };
+        if spec.get_value().is_some() {
+            let _ = parser.next_flag_value();
         }
+        given.extend(spec.get_long());
     }
-    Ok(expanded)
+    given
 }
 
-fn parse_argfile(path: &std::path::Path) -> std::io::Result<Vec<std::ffi::OsString>> {
-    // Logic taken from rust-lang/rust's `compiler/rustc_driver_impl/src/args.rs`
-    let content = std::fs::read_to_string(path)?;
-    Ok(content.lines().map(|s| s.into()).collect())
+/// Like libtest, accept abbreviations, e.g. `--ign` for `--ignored`
+fn resolve_prefix<'a>(
+    arg: cli::Arg<'a>,
+    options: &[cli::OptSpec],
+) -> Result<cli::Arg<'a>, cli::LexError<'a>> {
+    if cfg!(feature = "abbreviations") {
+        cli::resolve_prefix(arg, options)
+    } else {
+        Ok(arg)
+    }
 }
 
-fn parse<'p>(parser: &mut cli::Parser<'p>) -> Result<libtest_lexarg::TestOpts, cli::LexError<'p>> {
-    let mut test_opts = libtest_lexarg::TestOptsBuilder::new();
-
+fn parse<'p>(
+    parser: &mut cli::Parser<'p>,
+    options: &[cli::OptSpec],
+    given: &[&str],
+    mut test_opts: libtest_lexarg::TestOptsBuilder,
+    extensions: &mut [&mut dyn ArgParser],
+) -> Result<libtest_lexarg::TestOpts, cli::LexError<'p>> {
     let bin = parser
         .next_raw()
         .expect("first arg, no pending values")
         .unwrap_or(std::ffi::OsStr::new("test"));
     let mut prev_arg = cli::Arg::Value(bin);
-    while let Some(arg) = parser.next_arg() {
+    'args: while let Some(arg) = parser.next_arg() {
+        // `arg` may no longer point into the raw arguments once resolved
+        let location = parser.location().expect("`next_arg` returned an arg");
+        let arg = resolve_prefix(arg, options)?;
         match arg {
             cli::Arg::Short("h") | cli::Arg::Long("help") => {
-                let mut bin = std::path::Path::new(bin);
-                if let Ok(current_dir) = std::env::current_dir() {
-                    // abbreviate the path because cargo always uses absolute paths
-                    bin = bin.strip_prefix(&current_dir).unwrap_or(bin);
-                }
-                let bin = bin.to_string_lossy();
-                let options_help = libtest_lexarg::OPTIONS_HELP.trim();
-                let after_help = libtest_lexarg::AFTER_HELP.trim();
-                println!(
-                    "Usage: {bin} [OPTIONS] [FILTER]...
-
-{options_help}
-
-{after_help}"
-                );
+                let bin = display_bin(std::path::Path::new(bin));
+                let help = cli::Help::new(&bin)
+                    .specs(options)
+                    .after_help(libtest_lexarg::AFTER_HELP);
+                print!("{help}");
                 std::process::exit(0);
             }
+            // Hidden as this is only needed when setting up a shell
+            cli::Arg::Long("generate-completions") => {
+                use lexarg::prelude::*;
+
+                let shell = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("SHELL")))
+                    .one_of(complete::SHELLS)
+                    .within(arg)
+                    .map_err(|err| err.located(location))?;
+                let bin = std::path::Path::new(bin)
+                    .file_name()
+                    .unwrap_or(bin)
+                    .to_string_lossy();
+                let mut stdout = std::io::stdout().lock();
+                let _ = complete::write(shell, &bin, options, &mut stdout);
+                std::process::exit(0);
+            }
+            // Already handled, see `given_flags`
+            cli::Arg::Long("no-config") => {
+                prev_arg = arg;
+                continue;
+            }
             // All values are the same, whether escaped or not, so its a no-op
             cli::Arg::Escape(_) => {
                 prev_arg = arg;
@@ -236,14 +376,33 @@ fn parse<'p>(parser: &mut cli::Parser<'p>) -> Result<libtest_lexarg::TestOpts, c
         }
         prev_arg = arg;
 
-        let arg = test_opts.parse_next(parser, arg)?;
-
-        if let Some(arg) = arg {
-            return Err(cli::LexError::msg("unexpected argument").unexpected(arg));
+        // A parser may consume values before deciding `arg` isn't for it, so rewind before
+        // offering `arg` to the next parser
+        let checkpoint = parser.checkpoint();
+        let Some(mut arg) = test_opts
+            .parse_next(parser, arg)
+            .map_err(|err| err.located(location.clone()))?
+        else {
+            continue;
+        };
+        parser.restore(checkpoint);
+        for extension in extensions.iter_mut() {
+            let Some(unused) = extension
+                .parse_next(parser, arg)
+                .map_err(|err| err.located(location.clone()))?
+            else {
+                continue 'args;
+            };
+            parser.restore(checkpoint);
+            arg = unused;
         }
+
+        let error = cli::LexError::msg("unexpected argument").unexpected(arg);
+        return Err(cli::suggest_long(error, arg, options));
     }
 
     let mut opts = test_opts.finish()?;
+    env::apply(given, &mut opts)?;
     // If the platform is single-threaded we're just going to run
     // the test synchronously, regardless of the concurrency
     // level.
@@ -257,7 +416,16 @@ fn parse<'p>(parser: &mut cli::Parser<'p>) -> Result<libtest_lexarg::TestOpts, c
     Ok(opts)
 }
 
-fn notifier(opts: &libtest_lexarg::TestOpts) -> notify::ArcNotifier {
+/// Abbreviate the path to the test binary, as cargo always uses absolute paths
+fn display_bin(bin: &std::path::Path) -> String {
+    let bin = std::env::current_dir()
+        .ok()
+        .and_then(|current_dir| bin.strip_prefix(current_dir).ok())
+        .unwrap_or(bin);
+    bin.to_string_lossy().into_owned()
+}
+
+fn notifier(opts: &libtest_lexarg::TestOpts, bin: String) -> notify::ArcNotifier {
     #[cfg(feature = "color")]
     let stdout = anstream::stdout();
     #[cfg(not(feature = "color"))]
@@ -265,14 +433,42 @@ fn notifier(opts: &libtest_lexarg::TestOpts) -> notify::ArcNotifier {
     match opts.format {
         OutputFormat::Json => notify::ArcNotifier::new(notify::JsonNotifier::new(stdout)),
         _ if opts.list => notify::ArcNotifier::new(notify::TerseListNotifier::new(stdout)),
-        OutputFormat::Pretty => notify::ArcNotifier::new(notify::PrettyRunNotifier::new(stdout)),
-        OutputFormat::Terse => notify::ArcNotifier::new(notify::TerseRunNotifier::new(stdout)),
+        OutputFormat::Pretty => notify::ArcNotifier::new(
+            notify::PrettyRunNotifier::new(stdout).with_reproduce(bin, reproduce_args(opts)),
+        ),
+        OutputFormat::Terse => notify::ArcNotifier::new(
+            notify::TerseRunNotifier::new(stdout).with_reproduce(bin, reproduce_args(opts)),
+        ),
+    }
+}
+
+/// Arguments that a case selected by `--exact` needs to be run again
+fn reproduce_args(opts: &libtest_lexarg::TestOpts) -> Vec<String> {
+    let mut args = Vec::new();
+    if opts.bench_benchmarks {
+        args.push("--bench".to_owned());
+    }
+    match opts.run_ignored {
+        libtest_lexarg::RunIgnored::Yes => args.push("--include-ignored".to_owned()),
+        libtest_lexarg::RunIgnored::Only => args.push("--ignored".to_owned()),
+        libtest_lexarg::RunIgnored::No => {}
     }
+    args
 }
 
 fn case_priority(case: &dyn Case, opts: &libtest_lexarg::TestOpts) -> Option<usize> {
     let filtered_out =
         !opts.skip.is_empty() && opts.skip.iter().any(|sf| matches_filter(case, sf, opts));
+    let filtered_out = filtered_out
+        || opts
+            .exclude_tags
+            .iter()
+            .any(|tags| matches_tags(case, tags))
+        || (!opts.include_tags.is_empty()
+            && !opts
+                .include_tags
+                .iter()
+                .any(|tags| matches_tags(case, tags)));
     if filtered_out {
         None
     } else if opts.filters.is_empty() {
@@ -284,6 +480,18 @@ fn case_priority(case: &dyn Case, opts: &libtest_lexarg::TestOpts) -> Option<usi
     }
 }
 
+/// Reorder `cases` so the same `seed` always produces the same order
+fn shuffle(cases: &mut [Box<dyn Case>], seed: u64) {
+    rng::Rng::new(seed).shuffle(cases);
+}
+
+/// Whether `case` has all of the `+`-separated `tags`
+fn matches_tags(case: &dyn Case, tags: &str) -> bool {
+    let case_tags = case.tags();
+    tags.split('+')
+        .all(|tag| case_tags.iter().any(|case_tag| case_tag == tag))
+}
+
 fn matches_filter(case: &dyn Case, filter: &str, opts: &libtest_lexarg::TestOpts) -> bool {
     let test_name = case.name();
 
@@ -297,21 +505,19 @@ fn run(
     start: &std::time::Instant,
     opts: &libtest_lexarg::TestOpts,
     cases: Vec<Box<dyn Case>>,
+    seed: u64,
     notifier: notify::ArcNotifier,
+    hooks: std::sync::Arc<dyn Hooks>,
 ) -> std::io::Result<bool> {
     notifier.notify(
         notify::event::RunStart {
+            seed: Some(seed),
             elapsed_s: Some(notify::Elapsed(start.elapsed())),
         }
         .into(),
     )?;
 
-    if opts.no_capture {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "`--no-capture` is not supported at this time",
-        ));
-    }
+    // Output is never captured, so `--no-capture` is always in effect
     if opts.show_output {
         return Err(std::io::Error::new(
             std::io::ErrorKind::Unsupported,
@@ -341,10 +547,22 @@ fn run(
         run_ignored,
         notifier,
         test_name: String::new(),
+        seed,
+        seed_used: Default::default(),
+        attachments: Default::default(),
     };
 
     let mut success = true;
 
+    let cases = match run_hook(|| hooks.before_all()) {
+        Ok(()) => cases,
+        Err(err) => {
+            success = false;
+            notify_hook_failed(&context, "before_all", &err)?;
+            vec![]
+        }
+    };
+
     let (exclusive_cases, concurrent_cases) = if threads == 1 || cases.len() == 1 {
         (cases, vec![])
     } else {
@@ -375,11 +593,15 @@ fn run(
                 let cfg = std::thread::Builder::new().name(name.clone());
                 let thread_tx = tx.clone();
                 let thread_case = case.clone();
-                let mut thread_context = context.clone();
-                thread_context.test_name = name.clone();
+                let thread_context = context.clone();
                 let thread_sync_success = sync_success.clone();
+                let thread_hooks = hooks.clone();
                 let join_handle = cfg.spawn(move || {
-                    let status = run_case(thread_case.as_ref().as_ref(), &thread_context);
+                    let status = run_case(
+                        thread_case.as_ref().as_ref(),
+                        &thread_context,
+                        thread_hooks.as_ref(),
+                    );
                     if !matches!(status, Ok(true)) {
                         thread_sync_success.store(false, std::sync::atomic::Ordering::Relaxed);
                     }
@@ -393,7 +615,8 @@ fn run(
                     Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                         // `ErrorKind::WouldBlock` means hitting the thread limit on some
                         // platforms, so run the test synchronously here instead.
-                        let case_success = run_case(case.as_ref().as_ref(), &context)?;
+                        let case_success =
+                            run_case(case.as_ref().as_ref(), &context, hooks.as_ref())?;
                         if !case_success {
                             sync_success.store(case_success, std::sync::atomic::Ordering::Relaxed);
                         }
@@ -412,18 +635,41 @@ fn run(
                 break;
             }
         }
+        // With `--fail-fast`, let in-flight tests finish before tearing down
+        for (_, running_test) in running {
+            let _ = running_test.join();
+        }
+        success &= sync_success.load(std::sync::atomic::Ordering::SeqCst);
     }
 
-    if !exclusive_cases.is_empty() {
+    if !exclusive_cases.is_empty() && (success || !opts.fail_fast) {
         context.notifier().threaded(false);
         for case in exclusive_cases {
-            success &= run_case(case.as_ref(), &context)?;
+            success &= run_case(case.as_ref(), &context, hooks.as_ref())?;
             if !success && opts.fail_fast {
                 break;
             }
         }
     }
 
+    let teardown =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| hooks.teardown_fixtures()))
+            .unwrap_or_else(|e| {
+                vec![RunError::fail(panic_message(
+                    "fixture teardown",
+                    e.as_ref(),
+                ))]
+            });
+    for err in teardown {
+        success = false;
+        notify_fixture_failed(&context, &err)?;
+    }
+
+    if let Err(err) = run_hook(|| hooks.after_all()) {
+        success = false;
+        notify_hook_failed(&context, "after_all", &err)?;
+    }
+
     context.notifier().notify(
         notify::event::RunComplete {
             elapsed_s: Some(notify::Elapsed(start.elapsed())),
@@ -434,7 +680,11 @@ fn run(
     Ok(success)
 }
 
-fn run_case(case: &dyn Case, context: &TestContext) -> std::io::Result<bool> {
+fn run_case(case: &dyn Case, context: &TestContext, hooks: &dyn Hooks) -> std::io::Result<bool> {
+    let mut context = context.clone();
+    context.test_name = case.name().to_owned();
+    let context = &context;
+
     context.notifier().notify(
         notify::event::CaseStart {
             name: case.name().to_owned(),
@@ -443,24 +693,24 @@ fn run_case(case: &dyn Case, context: &TestContext) -> std::io::Result<bool> {
         .into(),
     )?;
 
-    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        __rust_begin_short_backtrace(|| case.run(context))
-    }))
-    .unwrap_or_else(|e| {
-        // The `panic` information is just an `Any` object representing the
-        // value the panic was invoked with. For most panics (which use
-        // `panic!` like `println!`), this is either `&str` or `String`.
-        let payload = e
-            .downcast_ref::<String>()
-            .map(|s| s.as_str())
-            .or_else(|| e.downcast_ref::<&str>().copied());
-
-        let msg = match payload {
-            Some(payload) => format!("test panicked: {payload}"),
-            None => "test panicked".to_owned(),
-        };
-        Err(RunError::fail(msg))
-    });
+    // Like libtest, only benchmarks are run with `--bench`
+    let outcome = if context.current_mode() == RunMode::Bench && case.mode() != RunMode::Bench {
+        Err(RunError::ignore())
+    } else {
+        let outcome = run_hook(|| hooks.before_each(context))
+            .map_err(|err| hook_failed("before_each", err))
+            .and_then(|()| {
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    __rust_begin_short_backtrace(|| case.run(context))
+                }))
+                .unwrap_or_else(|e| Err(RunError::fail(panic_message("test", e.as_ref()))))
+            });
+        // The test's own failure takes precedence
+        match (outcome, run_hook(|| hooks.after_each(context))) {
+            (Ok(()), Err(err)) => Err(hook_failed("after_each", err)),
+            (outcome, _) => outcome,
+        }
+    };
 
     let mut case_status = None;
     if let Some(err) = outcome.as_ref().err() {
@@ -481,12 +731,67 @@ fn run_case(case: &dyn Case, context: &TestContext) -> std::io::Result<bool> {
     context.notifier().notify(
         notify::event::CaseComplete {
             name: case.name().to_owned(),
+            seed: context.used_seed(),
             elapsed_s: Some(context.elapsed_s()),
         }
         .into(),
     )?;
 
-    Ok(case_status != Some(notify::MessageKind::Error))
+    Ok(!matches!(
+        case_status,
+        Some(notify::MessageKind::Fixture | notify::MessageKind::Error)
+    ))
+}
+
+/// Call a [`Hooks`] method, treating a panic as a failure
+fn run_hook(hook: impl FnOnce() -> crate::RunResult) -> crate::RunResult {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(hook))
+        .unwrap_or_else(|e| Err(RunError::fail(panic_message("hook", e.as_ref()))))
+}
+
+/// Report a failed per-test hook as a fixture failure, unless it ignored the test
+fn hook_failed(hook: &str, err: RunError) -> RunError {
+    match err.status() {
+        notify::MessageKind::Ignored => err,
+        _ => RunError::fixture(format_args!("`{hook}` failed: {err}")),
+    }
+}
+
+fn notify_hook_failed(context: &TestContext, hook: &str, err: &RunError) -> std::io::Result<()> {
+    context.notifier().notify(
+        notify::event::RunMessage {
+            kind: notify::MessageKind::Fixture,
+            message: Some(format!("`{hook}` failed: {err}")),
+            elapsed_s: Some(context.elapsed_s()),
+        }
+        .into(),
+    )
+}
+
+fn notify_fixture_failed(context: &TestContext, err: &RunError) -> std::io::Result<()> {
+    context.notifier().notify(
+        notify::event::RunMessage {
+            kind: notify::MessageKind::Fixture,
+            message: Some(err.to_string()),
+            elapsed_s: Some(context.elapsed_s()),
+        }
+        .into(),
+    )
+}
+
+fn panic_message(what: &str, payload: &(dyn std::any::Any + Send)) -> String {
+    // The `panic` information is just an `Any` object representing the
+    // value the panic was invoked with. For most panics (which use
+    // `panic!` like `println!`), this is either `&str` or `String`.
+    let payload = payload
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| payload.downcast_ref::<&str>().copied());
+
+    match payload {
+        Some(payload) => format!("{what} panicked: {payload}"),
+        None => format!("{what} panicked"),
+    }
 }
 
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`.
@@ -0,0 +1,44 @@
+pub(crate) use crate::*;
+
+/// Code to run around the test run, see [`Harness::run_with_hooks`]
+///
+/// Hooks are skipped when only listing tests.
+pub trait Hooks: Send + Sync + 'static {
+    /// Called once before any test runs
+    ///
+    /// On failure, no tests are run.
+    fn before_all(&self) -> RunResult {
+        Ok(())
+    }
+
+    /// Called once after all tests have run, even if they or [`Hooks::before_all`] failed
+    fn after_all(&self) -> RunResult {
+        Ok(())
+    }
+
+    /// Release resources shared between tests, called once right before [`Hooks::after_all`]
+    ///
+    /// Each error is reported and fails the run.
+    fn teardown_fixtures(&self) -> Vec<RunError> {
+        Vec::new()
+    }
+
+    /// Called before each test
+    ///
+    /// On failure, the test is not run and is reported as failing.
+    fn before_each(&self, context: &TestContext) -> RunResult {
+        let _ = context;
+        Ok(())
+    }
+
+    /// Called after each test, even if it failed
+    ///
+    /// On failure, the test is reported as failing unless it already had.
+    fn after_each(&self, context: &TestContext) -> RunResult {
+        let _ = context;
+        Ok(())
+    }
+}
+
+/// No hooks
+impl Hooks for () {}
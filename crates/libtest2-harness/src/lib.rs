@@ -5,18 +5,30 @@
 // #![warn(clippy::print_stdout)]
 
 mod case;
+mod complete;
+mod config;
 mod context;
+mod env;
 mod error;
+mod extension;
 mod harness;
+mod hooks;
 mod notify;
+mod replay;
+mod rng;
 
 pub mod cli;
 
 pub use case::*;
 pub use context::*;
 pub use error::*;
+pub use extension::*;
 pub use harness::*;
+pub use hooks::*;
+pub use notify::OutputStream;
 pub use notify::RunMode;
+pub use replay::replay;
+pub use rng::Rng;
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
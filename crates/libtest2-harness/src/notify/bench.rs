@@ -0,0 +1,45 @@
+use super::event::Extension;
+
+/// What a benchmark measured, see [`TestContext::notify_bench`][crate::TestContext::notify_bench]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Bench {
+    pub(crate) median_ns: u128,
+    pub(crate) deviation_ns: u128,
+}
+
+impl Bench {
+    pub(crate) const EXTENSION: &'static str = "libtest2.bench";
+
+    pub(crate) fn to_payload(self) -> String {
+        format!(
+            r#"{{"median_ns":{},"deviation_ns":{}}}"#,
+            self.median_ns, self.deviation_ns
+        )
+    }
+
+    /// Read back what [`Bench::to_payload`] wrote
+    pub(crate) fn from_extension(extension: &Extension) -> Option<Self> {
+        if extension.extension != Self::EXTENSION {
+            return None;
+        }
+        let payload = extension.payload.as_ref()?.as_str();
+        let (median_ns, deviation_ns) = payload
+            .strip_prefix(r#"{"median_ns":"#)?
+            .strip_suffix('}')?
+            .split_once(r#","deviation_ns":"#)?;
+        Some(Self {
+            median_ns: median_ns.parse().ok()?,
+            deviation_ns: deviation_ns.parse().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Bench {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} ns/iter (+/- {})",
+            self.median_ns, self.deviation_ns
+        )
+    }
+}
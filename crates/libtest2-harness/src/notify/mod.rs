@@ -1,3 +1,4 @@
+mod bench;
 mod json;
 #[cfg(not(feature = "color"))]
 mod no_style;
@@ -7,6 +8,7 @@ mod style;
 mod summary;
 mod terse;
 
+pub(crate) use bench::*;
 pub(crate) use json::*;
 #[cfg(not(feature = "color"))]
 pub(crate) use no_style::*;
@@ -53,4 +55,5 @@ impl ArcNotifier {
 
 pub(crate) use libtest_json::*;
 
+pub use libtest_json::OutputStream;
 pub use libtest_json::RunMode;
@@ -9,3 +9,4 @@ impl std::fmt::Display for Style {
 pub(crate) const FAILED: Style = Style;
 pub(crate) const OK: Style = Style;
 pub(crate) const IGNORED: Style = Style;
+pub(crate) const BENCH: Style = Style;
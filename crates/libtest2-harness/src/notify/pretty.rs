@@ -1,5 +1,6 @@
+use super::report::CaseStatus;
 use super::Event;
-use super::MessageKind;
+use super::BENCH;
 use super::FAILED;
 use super::IGNORED;
 use super::OK;
@@ -21,6 +22,14 @@ impl<W: std::io::Write> PrettyRunNotifier<W> {
             name_width: 0,
         }
     }
+
+    /// The test binary and the arguments that selected the tests, for suggesting how to
+    /// reproduce failures
+    pub(crate) fn with_reproduce(mut self, bin: String, args: Vec<String>) -> Self {
+        self.summary.bin = Some(bin);
+        self.summary.args = args;
+        self
+    }
 }
 
 impl<W: std::io::Write> super::Notifier for PrettyRunNotifier<W> {
@@ -52,12 +61,19 @@ impl<W: std::io::Write> super::Notifier for PrettyRunNotifier<W> {
                 }
             }
             Event::CaseMessage(_) => {}
+            Event::CaseOutput(_) => {}
+            Event::CaseAttachment(_) => {}
             Event::CaseComplete(inner) => {
-                let status = self.summary.get_kind(&inner.name);
-                let (s, style) = match status {
-                    Some(MessageKind::Ignored) => ("ignored", IGNORED),
-                    Some(MessageKind::Error) => ("FAILED", FAILED),
-                    None => ("ok", OK),
+                let status = self.summary.get_status(&inner.name);
+                let bench = match status {
+                    Some(CaseStatus::Ignored | CaseStatus::Failed) => None,
+                    _ => self.summary.get_bench(&inner.name),
+                };
+                let (s, style) = match (status, bench) {
+                    (Some(CaseStatus::Ignored), _) => ("ignored", IGNORED),
+                    (Some(CaseStatus::Failed), _) => ("FAILED", FAILED),
+                    (_, Some(_)) => ("bench", BENCH),
+                    _ => ("ok", OK),
                 };
 
                 if self.is_multithreaded {
@@ -67,11 +83,16 @@ impl<W: std::io::Write> super::Notifier for PrettyRunNotifier<W> {
                         inner.name, self.name_width
                     )?;
                 }
-                writeln!(self.writer, "{style}{s}{style:#}")?;
+                match bench {
+                    Some(bench) => writeln!(self.writer, "{style}{s}{style:#}: {bench}")?,
+                    None => writeln!(self.writer, "{style}{s}{style:#}")?,
+                }
             }
+            Event::RunMessage(_) => {}
             Event::RunComplete(_) => {
                 self.summary.write_complete(&mut self.writer)?;
             }
+            Event::Extension(_) => {}
         }
         Ok(())
     }
@@ -1,94 +1,157 @@
-use super::event::CaseMessage;
+use super::report::CaseStatus;
 use super::Event;
 use super::MessageKind;
+use super::OutputStream;
+use super::RunReport;
 use super::FAILED;
 use super::OK;
 
 #[derive(Default, Clone, Debug)]
 pub(crate) struct Summary {
-    num_run: usize,
-    /// Number of tests and benchmarks that were filtered out (either by the
-    /// filter-in pattern or by `--skip` arguments).
-    num_filtered_out: usize,
-
-    status: std::collections::HashMap<String, CaseStatus>,
-    elapsed_s: Option<super::Elapsed>,
+    report: RunReport,
+    pub(crate) bin: Option<String>,
+    /// Arguments needed for a test to be selected again, like `--include-ignored`
+    pub(crate) args: Vec<String>,
 }
 
 impl Summary {
-    pub(crate) fn get_kind(&self, name: &str) -> Option<MessageKind> {
-        let status = self.status.get(name)?;
-        find_run_status(status)
+    pub(crate) fn get_status(&self, name: &str) -> Option<CaseStatus> {
+        self.report.case(name).map(|c| c.status())
+    }
+
+    /// The last measurement reported for a benchmark
+    pub(crate) fn get_bench(&self, name: &str) -> Option<super::Bench> {
+        self.report
+            .extensions()
+            .iter()
+            .rev()
+            .filter(|e| e.name.as_deref() == Some(name))
+            .find_map(super::Bench::from_extension)
     }
 
     pub(crate) fn write_start(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
-        let s = if self.num_run == 1 { "" } else { "s" };
+        let num_run = self.report.totals().selected;
+        let s = if num_run == 1 { "" } else { "s" };
+
+        let shuffle_seed = self
+            .report
+            .shuffle_seed()
+            .map(|seed| format!(", shuffle seed: {seed}"))
+            .unwrap_or_default();
 
         writeln!(writer)?;
-        writeln!(writer, "running {} test{s}", self.num_run)?;
+        writeln!(writer, "running {num_run} test{s}{shuffle_seed}")?;
         Ok(())
     }
 
     pub(crate) fn write_complete(&self, writer: &mut dyn ::std::io::Write) -> std::io::Result<()> {
-        let mut num_passed = 0;
-        let mut num_failed = 0;
-        let mut num_ignored = 0;
-        let mut failures = std::collections::BTreeMap::new();
-        for (name, case_status) in &self.status {
-            let mut status = find_run_status(case_status);
-            if !case_status.started {
-                // Even override `Ignored`
-                status = Some(MessageKind::Error);
-                failures.insert(name, Some("test found that never started"));
-            }
-            if !case_status.completed {
-                // Even override `Ignored`
-                status = Some(MessageKind::Error);
-                failures.insert(name, Some("test never completed"));
-            }
-            match status {
-                Some(MessageKind::Ignored) => num_ignored += 1,
-                Some(MessageKind::Error) => {
-                    num_failed += 1;
-                    for event in &case_status.messages {
-                        if Some(event.kind) == status {
-                            failures.insert(name, event.message.as_deref());
-                        }
-                    }
-                }
-                None => num_passed += 1,
-            }
-        }
+        let totals = self.report.totals();
+        let failures = self
+            .report
+            .cases()
+            .filter(|c| c.status() == CaseStatus::Failed)
+            .collect::<Vec<_>>();
 
-        let has_failed = 0 < num_failed;
+        let run_failures = self
+            .report
+            .messages()
+            .iter()
+            .filter(|m| matches!(m.kind, MessageKind::Fixture | MessageKind::Error))
+            .collect::<Vec<_>>();
+
+        let has_failed = 0 < totals.failed || !run_failures.is_empty();
 
         let (summary, summary_style) = if has_failed {
             ("FAILED", FAILED)
         } else {
             ("ok", OK)
         };
-        let num_filtered_out = self.num_filtered_out;
-        let elapsed_s = self.elapsed_s;
+        let num_passed = totals.passed;
+        let num_failed = totals.failed;
+        let num_ignored = totals.ignored;
+        // Number of tests and benchmarks that were filtered out (either by the
+        // filter-in pattern or by `--skip` arguments).
+        let num_filtered_out = totals.filtered_out;
+        let elapsed_s = self.report.elapsed_s();
 
         if has_failed {
             writeln!(writer)?;
             writeln!(writer, "failures:")?;
             writeln!(writer)?;
 
-            // Print messages of all tests
-            for (name, msg) in &failures {
-                if let Some(msg) = msg {
+            // Print messages and captured output of all tests
+            //
+            // Only output from `TestContext::write_output` is here, as `print!` isn't captured
+            for case in &failures {
+                let name = case.name();
+                for stream in [OutputStream::Stdout, OutputStream::Stderr] {
+                    if let Some(content) = case.output(stream) {
+                        let stream = stream.as_str();
+                        writeln!(writer, "---- {name} {stream} ----")?;
+                        write!(writer, "{content}")?;
+                        if !content.ends_with('\n') {
+                            writeln!(writer)?;
+                        }
+                        writeln!(writer)?;
+                    }
+                }
+                if let Some(msg) = case.status_message() {
                     writeln!(writer, "---- {name} ----")?;
                     writeln!(writer, "{msg}")?;
                     writeln!(writer)?;
                 }
+                if let Some(seed) = case.seed() {
+                    writeln!(writer, "---- {name} seed ----")?;
+                    writeln!(writer, "seed: {seed}")?;
+                    if let Some(run_seed) = self.report.seed() {
+                        let args = self
+                            .args
+                            .iter()
+                            .map(|arg| shell_quote(arg))
+                            .chain([
+                                "--seed".into(),
+                                run_seed.to_string().into(),
+                                "--exact".into(),
+                                shell_quote(name),
+                            ])
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        match &self.bin {
+                            Some(bin) => {
+                                writeln!(writer, "reproduce with: {} {args}", shell_quote(bin))?;
+                            }
+                            None => writeln!(writer, "reproduce with: {args}")?,
+                        }
+                    }
+                    writeln!(writer)?;
+                }
+                if !case.attachments().is_empty() {
+                    writeln!(writer, "---- {name} attachments ----")?;
+                    for attachment in case.attachments() {
+                        let label = &attachment.attachment;
+                        let media_type = &attachment.media_type;
+                        let path = &attachment.path;
+                        writeln!(writer, "{label} ({media_type}): {path}")?;
+                    }
+                    writeln!(writer)?;
+                }
+            }
+
+            // Print what failed outside of any test, like a setup hook
+            for message in &run_failures {
+                let msg = message.message.as_deref().unwrap_or(message.kind.as_str());
+                writeln!(writer, "---- run ----")?;
+                writeln!(writer, "{msg}")?;
+                writeln!(writer)?;
             }
 
             // Print summary list of failed tests
-            writeln!(writer)?;
-            writeln!(writer, "failures:")?;
-            for name in failures.keys() {
-                writeln!(writer, "    {name}")?;
+            if !failures.is_empty() {
+                writeln!(writer)?;
+                writeln!(writer, "failures:")?;
+                for case in &failures {
+                    writeln!(writer, "    {}", case.name())?;
+                }
             }
         }
         writeln!(writer)?;
@@ -108,51 +171,22 @@ impl Summary {
     }
 }
 
-impl super::Notifier for Summary {
-    fn notify(&mut self, event: Event) -> std::io::Result<()> {
-        match event {
-            Event::DiscoverStart(_) => {}
-            Event::DiscoverCase(inner) => {
-                if inner.selected {
-                    self.num_run += 1;
-                } else {
-                    self.num_filtered_out += 1;
-                }
-            }
-            Event::DiscoverComplete(_) => {}
-            Event::RunStart(_) => {}
-            Event::CaseStart(inner) => {
-                self.status.entry(inner.name).or_default().started = true;
-            }
-            Event::CaseMessage(inner) => {
-                self.status
-                    .entry(inner.name.clone())
-                    .or_default()
-                    .messages
-                    .push(inner);
-            }
-            Event::CaseComplete(inner) => {
-                self.status.entry(inner.name).or_default().completed = true;
-            }
-            Event::RunComplete(inner) => {
-                self.elapsed_s = inner.elapsed_s;
-            }
-        }
-        Ok(())
+/// Quote `arg` for a POSIX shell, if needed
+pub(crate) fn shell_quote(arg: &str) -> std::borrow::Cow<'_, str> {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':'));
+    if is_plain {
+        arg.into()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''")).into()
     }
 }
 
-fn find_run_status(case_status: &CaseStatus) -> Option<MessageKind> {
-    let mut status = None;
-    for event in &case_status.messages {
-        status = status.max(Some(event.kind));
+impl super::Notifier for Summary {
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        self.report.push(event);
+        Ok(())
     }
-    status
-}
-
-#[derive(Default, Clone, Debug)]
-struct CaseStatus {
-    messages: Vec<CaseMessage>,
-    started: bool,
-    completed: bool,
 }
@@ -1,5 +1,6 @@
+use super::report::CaseStatus;
 use super::Event;
-use super::MessageKind;
+use super::BENCH;
 use super::FAILED;
 use super::IGNORED;
 use super::OK;
@@ -36,8 +37,12 @@ impl<W: std::io::Write> super::Notifier for TerseListNotifier<W> {
             Event::RunStart(_) => {}
             Event::CaseStart(_) => {}
             Event::CaseMessage(_) => {}
+            Event::CaseOutput(_) => {}
+            Event::CaseAttachment(_) => {}
             Event::CaseComplete(_) => {}
+            Event::RunMessage(_) => {}
             Event::RunComplete(_) => {}
+            Event::Extension(_) => {}
         }
         Ok(())
     }
@@ -56,6 +61,14 @@ impl<W: std::io::Write> TerseRunNotifier<W> {
             summary: Default::default(),
         }
     }
+
+    /// The test binary and the arguments that selected the tests, for suggesting how to
+    /// reproduce failures
+    pub(crate) fn with_reproduce(mut self, bin: String, args: Vec<String>) -> Self {
+        self.summary.bin = Some(bin);
+        self.summary.args = args;
+        self
+    }
 }
 
 impl<W: std::io::Write> super::Notifier for TerseRunNotifier<W> {
@@ -70,19 +83,34 @@ impl<W: std::io::Write> super::Notifier for TerseRunNotifier<W> {
             }
             Event::CaseStart(_) => {}
             Event::CaseMessage(_) => {}
+            Event::CaseOutput(_) => {}
+            Event::CaseAttachment(_) => {}
             Event::CaseComplete(inner) => {
-                let status = self.summary.get_kind(&inner.name);
+                let status = self.summary.get_status(&inner.name);
+                if let (Some(CaseStatus::Passed), Some(bench)) =
+                    (status, self.summary.get_bench(&inner.name))
+                {
+                    // Like libtest, measurements get their own line
+                    let name = &inner.name;
+                    writeln!(
+                        self.writer,
+                        "test {name} ... {BENCH}bench{BENCH:#}: {bench}"
+                    )?;
+                    return Ok(());
+                }
                 let (c, style) = match status {
-                    Some(MessageKind::Ignored) => ('i', IGNORED),
-                    Some(MessageKind::Error) => ('F', FAILED),
-                    None => ('.', OK),
+                    Some(CaseStatus::Ignored) => ('i', IGNORED),
+                    Some(CaseStatus::Failed) => ('F', FAILED),
+                    _ => ('.', OK),
                 };
                 write!(self.writer, "{style}{c}{style:#}")?;
                 self.writer.flush()?;
             }
+            Event::RunMessage(_) => {}
             Event::RunComplete(_) => {
                 self.summary.write_complete(&mut self.writer)?;
             }
+            Event::Extension(_) => {}
         }
         Ok(())
     }
@@ -0,0 +1,29 @@
+use libtest_lexarg::OutputFormat;
+
+use crate::notify;
+
+/// Render a recorded [`Event`][libtest_json::Event] stream as the test binary would have
+///
+/// Events from concurrently run cases may be interleaved.
+pub fn replay(
+    events: impl IntoIterator<Item = libtest_json::Event>,
+    format: OutputFormat,
+    writer: impl std::io::Write,
+) -> std::io::Result<()> {
+    match format {
+        OutputFormat::Pretty => replay_with(events, notify::PrettyRunNotifier::new(writer)),
+        OutputFormat::Terse => replay_with(events, notify::TerseRunNotifier::new(writer)),
+        OutputFormat::Json => replay_with(events, notify::JsonNotifier::new(writer)),
+    }
+}
+
+fn replay_with(
+    events: impl IntoIterator<Item = libtest_json::Event>,
+    mut notifier: impl notify::Notifier,
+) -> std::io::Result<()> {
+    notifier.threaded(true);
+    for event in events {
+        notifier.notify(event)?;
+    }
+    Ok(())
+}
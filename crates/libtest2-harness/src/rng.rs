@@ -0,0 +1,77 @@
+/// A small, deterministic random number generator, see [`TestContext::rng`][crate::TestContext::rng]
+///
+/// This is [SplitMix64](https://prng.di.unimi.it/splitmix64.c): fast and good enough for
+/// generating test inputs but not for cryptography.  The sequence for a seed is stable across
+/// releases so failures can be reproduced.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        mix(self.state)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// A value in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// A value in `range`
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty
+    pub fn next_range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {range:?}");
+        let span = range.end - range.start;
+        // Multiply-shift rather than `%` to avoid favoring the lower values as much
+        let offset = (u128::from(self.next_u64()) * u128::from(span)) >> 64;
+        range.start + offset as u64
+    }
+
+    /// Reorder `items` randomly
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_range(0..i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// The seed for a test, derived from the run's seed and the test's name
+pub(crate) fn case_seed(run_seed: u64, name: &str) -> u64 {
+    // FNV-1a, as `std`'s hashers aren't guaranteed to be stable across releases
+    let name_hash = name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    mix(run_seed ^ name_hash)
+}
+
+/// Generate a seed for a run, when the user didn't specify one
+pub(crate) fn run_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -54,7 +54,7 @@
 //!
 //! Some of the notable differences:
 //!
-//! - Output capture and `--no-capture`: simply not supported. The official
+//! - Output capture: simply not supported, so `--no-capture` is always in effect. The official
 //!   `libtest` uses internal `std` functions to temporarily redirect output.
 //!   `libtest-mimic` cannot use those, see also [libtest2#12](https://github.com/assert-rs/libtest2/issues/12)
 //! - `--format=json` (unstable): our schema is part of an experiment to see what should be
@@ -64,6 +64,8 @@
 //#![warn(clippy::print_stderr)]
 #![warn(clippy::print_stdout)]
 
+pub use libtest2_harness::OutputStream;
+
 pub struct Harness {
     raw: Vec<std::ffi::OsString>,
     cases: Vec<Trial>,
@@ -114,7 +116,7 @@ impl Harness {
         let harness = match harness.parse() {
             Ok(harness) => harness,
             Err(err) => {
-                eprintln!("{err}");
+                eprint!("{}", harness.render_error(&err));
                 std::process::exit(1);
             }
         };
@@ -126,6 +128,7 @@ impl Harness {
 /// A test case to be run
 pub struct Trial {
     name: String,
+    tags: Vec<String>,
     #[allow(clippy::type_complexity)]
     runner: Box<dyn Fn(RunContext<'_>) -> Result<(), RunError> + Send + Sync>,
 }
@@ -140,9 +143,16 @@ impl Trial {
     ) -> Self {
         Self {
             name: name.into(),
+            tags: Vec::new(),
             runner: Box::new(runner),
         }
     }
+
+    /// Label the test case for `--include-tag` and `--exclude-tag`
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
 }
 
 struct TrialCase {
@@ -162,6 +172,9 @@ impl libtest2_harness::Case for TrialCase {
     fn exclusive(&self, _: &libtest2_harness::TestContext) -> bool {
         false
     }
+    fn tags(&self) -> &[String] {
+        &self.inner.tags
+    }
 
     fn run(
         &self,
@@ -223,6 +236,48 @@ impl<'t> RunContext<'t> {
             .ignore_for(reason)
             .map_err(|e| RunError { inner: e })
     }
+
+    /// Report output captured from this test
+    ///
+    /// This is shown to the user if the test fails.
+    ///
+    /// `print!` and friends are not captured, so only output passed in here is reported.
+    pub fn write_output(
+        &self,
+        stream: OutputStream,
+        content: impl Into<String>,
+    ) -> std::io::Result<()> {
+        self.inner.write_output(stream, content)
+    }
+
+    /// Publish a file produced by this test, like a snapshot or screenshot
+    ///
+    /// `path` must exist and `name` must be unique within the test, see
+    /// [`libtest2_harness::TestContext::attach_file`].
+    pub fn attach_file(
+        &self,
+        name: impl Into<String>,
+        path: impl AsRef<std::path::Path>,
+        media_type: &str,
+    ) -> std::io::Result<()> {
+        self.inner.attach_file(name, path, media_type)
+    }
+
+    /// Publish content produced by this test, like a snapshot or screenshot
+    ///
+    /// The content is written to a temporary directory that outlives the test run.  Directories
+    /// left by runs from more than a day ago are removed.
+    ///
+    /// `name` must be unique within the test and may not be empty, `.`, or `..`, failing with
+    /// [`std::io::ErrorKind::AlreadyExists`] or [`std::io::ErrorKind::InvalidInput`] otherwise.
+    pub fn attach_bytes(
+        &self,
+        name: impl Into<String>,
+        content: impl AsRef<[u8]>,
+        media_type: &str,
+    ) -> std::io::Result<()> {
+        self.inner.attach_bytes(name, content, media_type)
+    }
 }
 
 #[doc = include_str!("../README.md")]
@@ -0,0 +1,378 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+fn main() {
+    use libtest2_mimic::RunError;
+    use libtest2_mimic::Trial;
+    libtest2_mimic::Harness::with_env()
+        .discover([
+            Trial::test("passes", |context| {
+                std::fs::write("snapshot.txt", "snapshot")?;
+                context.attach_file("snapshot", "snapshot.txt", "text/plain")?;
+                Ok(())
+            }),
+            Trial::test("fails", |context| {
+                std::fs::write("snapshot.txt", "snapshot")?;
+                context.attach_file("snapshot", "snapshot.txt", "text/plain")?;
+                context.attach_bytes("screenshot.png", [0x89, 0x50, 0x4e, 0x47], "image/png")?;
+                Err(RunError::fail("image mismatch"))
+            }),
+            Trial::test("duplicate", |context| {
+                context.attach_bytes("log", "first", "text/plain")?;
+                context.attach_bytes("log", "second", "text/plain")?;
+                Ok(())
+            }),
+            Trial::test("missing", |context| {
+                context.attach_file("missing", "missing.txt", "text/plain")?;
+                Ok(())
+            }),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 4 tests
+test duplicate ... FAILED
+test fails     ... FAILED
+test missing   ... FAILED
+test passes    ... ok
+
+failures:
+
+---- duplicate ----
+`log` is already attached
+
+---- duplicate attachments ----
+log (text/plain): [..]/duplicate/log
+
+---- fails ----
+image mismatch
+
+---- fails attachments ----
+snapshot (text/plain): [..]snapshot.txt
+screenshot.png (image/png): [..]/fails/screenshot.png
+
+---- missing ----
+`[..]missing.txt` does not exist
+
+
+failures:
+    duplicate
+    fails
+    missing
+
+test result: FAILED. 1 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 4 tests
+...
+
+failures:
+
+---- duplicate ----
+`log` is already attached
+
+---- duplicate attachments ----
+log (text/plain): [..]/duplicate/log
+
+---- fails ----
+image mismatch
+
+---- fails attachments ----
+snapshot (text/plain): [..]snapshot.txt
+screenshot.png (image/png): [..]/fails/screenshot.png
+
+---- missing ----
+`[..]missing.txt` does not exist
+
+
+failures:
+    duplicate
+    fails
+    missing
+
+test result: FAILED. 1 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "fails"],
+        101,
+        str![[r#"
+[
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_start"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "duplicate",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "missing",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_complete"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_start",
+    "seed": "{...}"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "fails"
+  },
+  {
+    "attachment": "snapshot",
+    "elapsed_s": "[..]",
+    "event": "case_attachment",
+    "media_type": "text/plain",
+    "name": "fails",
+    "path": "[..]snapshot.txt"
+  },
+  {
+    "attachment": "screenshot.png",
+    "elapsed_s": "[..]",
+    "event": "case_attachment",
+    "media_type": "image/png",
+    "name": "fails",
+    "path": "[..]/fails/screenshot.png"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_message",
+    "kind": "error",
+    "message": "image mismatch",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_complete"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_start"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "duplicate",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "missing",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_complete"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_start",
+    "seed": "{...}"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "fails"
+  },
+  {
+    "attachment": "snapshot",
+    "elapsed_s": "[..]",
+    "event": "case_attachment",
+    "media_type": "text/plain",
+    "name": "fails",
+    "path": "[..]snapshot.txt"
+  },
+  {
+    "attachment": "screenshot.png",
+    "elapsed_s": "[..]",
+    "event": "case_attachment",
+    "media_type": "image/png",
+    "name": "fails",
+    "path": "[..]/fails/screenshot.png"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_message",
+    "kind": "error",
+    "message": "image mismatch",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_complete"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
+
+#[test]
+fn invalid() {
+    check(
+        &["--exact", "duplicate", "missing"],
+        101,
+        str![[r#"
+
+running 2 tests
+test duplicate ... FAILED
+test missing   ... FAILED
+
+failures:
+
+---- duplicate ----
+`log` is already attached
+
+---- duplicate attachments ----
+log (text/plain): [..]/duplicate/log
+
+---- missing ----
+`[..]missing.txt` does not exist
+
+
+failures:
+    duplicate
+    missing
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 2 tests
+...
+
+failures:
+
+---- duplicate ----
+`log` is already attached
+
+---- duplicate attachments ----
+log (text/plain): [..]/duplicate/log
+
+---- missing ----
+`[..]missing.txt` does not exist
+
+
+failures:
+    duplicate
+    missing
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn attach_bytes_writes_content() {
+    let output = test_cmd()
+        .args(["-Zunstable-options", "--format=json", "--exact", "fails"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let path = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .filter(|e| e["attachment"] == "screenshot.png")
+        .map(|e| e["path"].as_str().unwrap().to_owned())
+        .next()
+        .unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), [0x89, 0x50, 0x4e, 0x47]);
+}
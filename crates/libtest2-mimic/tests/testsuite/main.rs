@@ -1,8 +1,11 @@
 mod all_passing;
 mod argfile;
+mod attachment;
 mod main_thread;
 mod mixed_bag;
+mod output;
 mod panic;
+mod tags;
 mod util;
 
 pub use util::*;
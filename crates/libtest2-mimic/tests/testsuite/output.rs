@@ -0,0 +1,331 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+fn main() {
+    use libtest2_mimic::OutputStream;
+    use libtest2_mimic::RunError;
+    use libtest2_mimic::Trial;
+    libtest2_mimic::Harness::with_env()
+        .discover([
+            Trial::test("passes", |context| {
+                context.write_output(OutputStream::Stdout, "hidden\n")?;
+                Ok(())
+            }),
+            Trial::test("fails", |context| {
+                context.write_output(OutputStream::Stdout, "hello ")?;
+                context.write_output(OutputStream::Stderr, "oh no")?;
+                context.write_output(OutputStream::Stdout, "world\n")?;
+                Err(RunError::fail("was not a good boy"))
+            }),
+            Trial::test("prints", |_context| {
+                println!("not captured");
+                Err(RunError::fail("printed"))
+            }),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 3 tests
+test fails  ... FAILED
+test passes ... ok
+test prints ... not captured
+FAILED
+
+failures:
+
+---- fails stdout ----
+hello world
+
+---- fails stderr ----
+oh no
+
+---- fails ----
+was not a good boy
+
+---- prints ----
+printed
+
+
+failures:
+    fails
+    prints
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 3 tests
+...
+
+failures:
+
+---- fails stdout ----
+hello world
+
+---- fails stderr ----
+oh no
+
+---- fails ----
+was not a good boy
+
+---- prints ----
+printed
+
+
+failures:
+    fails
+    prints
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn uncaptured() {
+    check(
+        &["prints"],
+        101,
+        str![[r#"
+
+running 1 test
+test prints ... not captured
+FAILED
+
+failures:
+
+---- prints ----
+printed
+
+
+failures:
+    prints
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 1 test
+test prints ... not captured
+FAILED
+
+failures:
+
+---- prints ----
+printed
+
+
+failures:
+    prints
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "fails"],
+        101,
+        str![[r#"
+[
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_start"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "prints",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_complete"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_start",
+    "seed": "{...}"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "fails"
+  },
+  {
+    "content": "hello ",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stdout"
+  },
+  {
+    "content": "oh no",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stderr"
+  },
+  {
+    "content": "world\n",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stdout"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_message",
+    "kind": "error",
+    "message": "was not a good boy",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_complete"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_start"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "prints",
+    "selected": false
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_complete"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_start",
+    "seed": "{...}"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "fails"
+  },
+  {
+    "content": "hello ",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stdout"
+  },
+  {
+    "content": "oh no",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stderr"
+  },
+  {
+    "content": "world\n",
+    "elapsed_s": "[..]",
+    "event": "case_output",
+    "name": "fails",
+    "stream": "stdout"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_message",
+    "kind": "error",
+    "message": "was not a good boy",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "fails"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_complete"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
@@ -0,0 +1,162 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+fn main() {
+    use libtest2_mimic::Trial;
+    libtest2_mimic::Harness::with_env()
+        .discover([
+            Trial::test("untagged", |_| Ok(())),
+            Trial::test("fetch", |_| Ok(())).with_tags(["network"]),
+            Trial::test("download", |_| Ok(())).with_tags(["network", "slow"]),
+            Trial::test("compress", |_| Ok(())).with_tags(["slow"]),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn include() {
+    test_cmd()
+        .args(["--include-tag", "network", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test download ... ok
+test fetch    ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn include_any() {
+    test_cmd()
+        .args([
+            "--include-tag",
+            "network",
+            "--include-tag",
+            "slow",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 3 tests
+test compress ... ok
+test download ... ok
+test fetch    ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn include_all() {
+    test_cmd()
+        .args(["--include-tag", "network+slow", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test download ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn exclude() {
+    test_cmd()
+        .args(["--exclude-tag", "slow", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test fetch    ... ok
+test untagged ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn include_and_exclude() {
+    test_cmd()
+        .args([
+            "--include-tag",
+            "network",
+            "--exclude-tag",
+            "slow",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test fetch ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn json() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=json",
+            "--exclude-tag",
+            "network+slow",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"untagged","elapsed_s":"[..]"}
+{"event":"discover_case","name":"fetch","tags":["network"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"download","selected":false,"tags":["network","slow"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"compress","tags":["slow"],"elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+{"event":"run_start","seed":[..],"elapsed_s":"[..]"}
+{"event":"case_start","name":"compress","elapsed_s":"[..]"}
+{"event":"case_complete","name":"compress","elapsed_s":"[..]"}
+{"event":"case_start","name":"fetch","elapsed_s":"[..]"}
+{"event":"case_complete","name":"fetch","elapsed_s":"[..]"}
+{"event":"case_start","name":"untagged","elapsed_s":"[..]"}
+{"event":"case_complete","name":"untagged","elapsed_s":"[..]"}
+{"event":"run_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
@@ -12,6 +12,12 @@ pub fn test(attribute: TokenStream, item: TokenStream) -> TokenStream {
     generate("test", "libtest2", attribute, item)
 }
 
+#[allow(missing_docs)]
+#[proc_macro_attribute]
+pub fn bench(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    generate("bench", "libtest2", attribute, item)
+}
+
 #[allow(missing_docs)]
 #[proc_macro_attribute]
 pub fn main(attribute: TokenStream, item: TokenStream) -> TokenStream {
@@ -87,7 +93,17 @@ fn generate(
         ]);
     }
 
-    inner.extend(item);
+    if macro_type == "test" {
+        if let Err(error) = check_cases(&item) {
+            return error;
+        }
+    }
+    if macro_type == "test" || macro_type == "bench" {
+        inner.extend(source(&item));
+        inner.extend(label_cases(item));
+    } else {
+        inner.extend(item);
+    }
 
     let mut invoke = crate_path.unwrap_or_else(|| {
         TokenStream::from_iter([
@@ -114,3 +130,281 @@ fn generate(
 
     invoke
 }
+
+/// Label each `#[case(...)]` by its position, like `#[case::case_1(...)]`
+///
+/// `macro_rules` can't count so this is done before handing off.
+fn label_cases(item: TokenStream) -> TokenStream {
+    let mut index = 0;
+    item.into_iter()
+        .map(|token| {
+            let TokenTree::Group(attr) = &token else {
+                return token;
+            };
+            if attr.delimiter() != Delimiter::Bracket {
+                return token;
+            }
+            let contents = attr.stream().into_iter().collect::<Vec<_>>();
+            match contents.as_slice() {
+                [TokenTree::Ident(case), TokenTree::Group(args)]
+                    if case.to_string() == "case" && args.delimiter() == Delimiter::Parenthesis =>
+                {
+                    index += 1;
+                    let span = case.span();
+                    let mut labeled = Group::new(
+                        Delimiter::Bracket,
+                        TokenStream::from_iter([
+                            TokenTree::Ident(case.clone()),
+                            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                            TokenTree::Ident(Ident::new(&format!("case_{index}"), span)),
+                            TokenTree::Group(args.clone()),
+                        ]),
+                    );
+                    labeled.set_span(attr.span());
+                    TokenTree::Group(labeled)
+                }
+                [TokenTree::Ident(case), TokenTree::Punct(colon), ..]
+                    if case.to_string() == "case" && colon.as_char() == ':' =>
+                {
+                    index += 1;
+                    token
+                }
+                _ => token,
+            }
+        })
+        .collect()
+}
+
+/// Reject `#[case]` where its values can't be passed straight to the function
+///
+/// Rows bypass fixtures and executors, calling the function with the `TestContext` followed by
+/// the row's values.
+fn check_cases(item: &TokenStream) -> Result<(), TokenStream> {
+    let tokens = item.clone().into_iter().collect::<Vec<_>>();
+    let cases = tokens
+        .iter()
+        .filter_map(|token| match token {
+            TokenTree::Group(attr) if attr.delimiter() == Delimiter::Bracket => Some(attr),
+            _ => None,
+        })
+        .filter_map(|attr| {
+            let contents = attr.stream().into_iter().collect::<Vec<_>>();
+            match (contents.first(), contents.last()) {
+                (Some(TokenTree::Ident(case)), Some(TokenTree::Group(args)))
+                    if case.to_string() == "case" && args.delimiter() == Delimiter::Parenthesis =>
+                {
+                    Some((attr.span(), args.stream()))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    if cases.is_empty() {
+        return Ok(());
+    }
+
+    let Some(fn_index) = tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == "fn"))
+    else {
+        return Ok(());
+    };
+    if let Some(TokenTree::Ident(asyncness)) = tokens[..fn_index]
+        .iter()
+        .find(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == "async"))
+    {
+        return Err(compile_error(
+            asyncness.span(),
+            "'case' is not supported on an 'async fn'",
+        ));
+    }
+
+    let Some(params) = tokens[fn_index..].iter().find_map(|t| match t {
+        TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis => Some(g.stream()),
+        _ => None,
+    }) else {
+        return Ok(());
+    };
+    let Some(expected) = count_params(params).checked_sub(1) else {
+        return Ok(());
+    };
+    for (span, args) in cases {
+        if count_args(args) != expected {
+            return Err(compile_error(
+                span,
+                &format!(
+                    "'case' expects {expected} value(s), one for each parameter after the 'TestContext'; fixtures are not supported with 'case'"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Count the comma-separated parameters of a function, skipping commas within generics
+fn count_params(params: TokenStream) -> usize {
+    let mut count = 0;
+    let mut depth = 0_usize;
+    let mut empty = true;
+    let mut prev = None;
+    for token in params {
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            // Not the end of `->`
+            TokenTree::Punct(p)
+                if p.as_char() == '>'
+                    && !matches!(&prev, Some(TokenTree::Punct(prev)) if prev.as_char() == '-') =>
+            {
+                depth = depth.saturating_sub(1);
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                count += 1;
+                empty = true;
+                prev = Some(token);
+                continue;
+            }
+            _ => {}
+        }
+        empty = false;
+        prev = Some(token);
+    }
+    if !empty {
+        count += 1;
+    }
+    count
+}
+
+/// Count the comma-separated expressions of a `#[case]`
+///
+/// Commas within closure parameters, like `|a, b| a + b`, and within generics, like
+/// `HashMap::<u8, u8>::new()`, don't separate expressions.  As `<` is also less-than, it only
+/// starts generics after `::`, `as`, at the start of an expression, or within other generics.
+fn count_args(args: TokenStream) -> usize {
+    let mut count = 0;
+    let mut depth = 0_usize;
+    let mut in_closure_params = false;
+    let mut empty = true;
+    let mut prev: Option<TokenTree> = None;
+    for token in args {
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == '|' && depth == 0 => {
+                let starts_closure =
+                    empty || matches!(&prev, Some(TokenTree::Ident(i)) if i.to_string() == "move");
+                if starts_closure || in_closure_params {
+                    in_closure_params = !in_closure_params;
+                }
+            }
+            TokenTree::Punct(p) if p.as_char() == '<' => {
+                let starts_generics = empty
+                    || 0 < depth
+                    || match &prev {
+                        Some(TokenTree::Punct(prev)) => prev.as_char() == ':',
+                        Some(TokenTree::Ident(prev)) => prev.to_string() == "as",
+                        _ => false,
+                    };
+                if starts_generics {
+                    depth += 1;
+                }
+            }
+            // Not the end of `->`
+            TokenTree::Punct(p)
+                if p.as_char() == '>'
+                    && !matches!(&prev, Some(TokenTree::Punct(prev)) if prev.as_char() == '-') =>
+            {
+                depth = depth.saturating_sub(1);
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 && !in_closure_params => {
+                count += 1;
+                empty = true;
+                prev = Some(token);
+                continue;
+            }
+            _ => {}
+        }
+        empty = false;
+        prev = Some(token);
+    }
+    if !empty {
+        count += 1;
+    }
+    count
+}
+
+/// `::core::compile_error!("msg");`, spanned at `span`
+fn compile_error(span: Span, msg: &str) -> TokenStream {
+    let mut msg = proc_macro::Literal::string(msg);
+    msg.set_span(span);
+    let mut error = invoke_builtin("compile_error", span, TokenTree::Literal(msg).into());
+    let mut semi = Punct::new(';', Spacing::Alone);
+    semi.set_span(span);
+    error.extend([TokenTree::Punct(semi)]);
+    error
+}
+
+/// Locate the function, like `source=[file!(), line!(), column!(), line!(), column!()]`
+///
+/// The macros report where they are invoked, so they are spanned at the `fn` keyword and the
+/// closing brace of the body rather than at `#[test]`.
+fn source(item: &TokenStream) -> TokenStream {
+    let mut start = Span::call_site();
+    let mut end = Span::call_site();
+    for token in item.clone() {
+        match &token {
+            TokenTree::Ident(ident) if ident.to_string() == "fn" => start = ident.span(),
+            TokenTree::Group(body) if body.delimiter() == Delimiter::Brace => {
+                end = body.span_close();
+            }
+            _ => {}
+        }
+    }
+
+    let mut location = TokenStream::new();
+    for (i, (name, span)) in [
+        ("file", start),
+        ("line", start),
+        ("column", start),
+        ("line", end),
+        ("column", end),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if i != 0 {
+            location.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        location.extend(invoke_builtin(name, span, TokenStream::new()));
+    }
+
+    TokenStream::from_iter([
+        TokenTree::Ident(Ident::new("source", Span::call_site())),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, location)),
+    ])
+}
+
+/// `::core::{name}!(args)`, with every token at `span`
+fn invoke_builtin(name: &str, span: Span, args: TokenStream) -> TokenStream {
+    let mut args = Group::new(Delimiter::Parenthesis, args);
+    args.set_span(span);
+    let mut colons = [
+        Punct::new(':', Spacing::Joint),
+        Punct::new(':', Spacing::Alone),
+    ];
+    for colon in &mut colons {
+        colon.set_span(span);
+    }
+    let [first, second] = colons;
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    TokenStream::from_iter([
+        TokenTree::Punct(first.clone()),
+        TokenTree::Punct(second.clone()),
+        TokenTree::Ident(Ident::new("core", span)),
+        TokenTree::Punct(first),
+        TokenTree::Punct(second),
+        TokenTree::Ident(Ident::new(name, span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(args),
+    ])
+}
@@ -0,0 +1,279 @@
+//! Render a recorded libtest2 json event log like a test run
+//!
+//! Reads newline-delimited [`Event`]s, as written by `--format json`, and prints them as
+//! the `pretty` or `terse` output the test binary would have shown.
+
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+use lexarg::LexError;
+use lexarg::OptSpec;
+use lexarg::PossibleValue;
+use libtest_json::report::CaseStatus;
+use libtest_json::Event;
+use libtest_json::RunReport;
+use libtest_lexarg::OutputFormat;
+
+const OPTIONS: &[OptSpec] = &[
+    OptSpec::positional("FILE")
+        .required()
+        .help("Event log from `--format json`, `-` for stdin"),
+    OptSpec::positional("FILTER")
+        .multiple()
+        .help("Only show tests whose name matches one of the filters"),
+    OptSpec::new()
+        .long("skip")
+        .value("FILTER")
+        .multiple()
+        .help("Hide tests whose names contain FILTER"),
+    OptSpec::new()
+        .long("exact")
+        .help("Exactly match filters rather than by substring"),
+    OptSpec::new()
+        .long("status")
+        .value("STATUS")
+        .possible(&[
+            PossibleValue::new("passed"),
+            PossibleValue::new("failed"),
+            PossibleValue::new("ignored"),
+        ])
+        .multiple()
+        .help("Only show tests with this status"),
+    OptSpec::new()
+        .long("format")
+        .value("FORMAT")
+        .possible(&[
+            PossibleValue::new("pretty").help("Print verbose output"),
+            PossibleValue::new("terse").help("Display one character per test"),
+        ])
+        .help("Configure formatting of output:"),
+    OptSpec::new()
+        .short("q")
+        .long("quiet")
+        .help("Display one character per test instead of one line\n(alias to --format=terse)"),
+    OptSpec::new()
+        .long("color")
+        .value("WHEN")
+        .possible(&[
+            PossibleValue::new("auto").help("detect terminal support (default)"),
+            PossibleValue::new("always").help("always colorize output"),
+            PossibleValue::new("never").help("never colorize output"),
+        ])
+        .help("Configure coloring of output:"),
+    OptSpec::new().short("h").long("help").help("Print help"),
+];
+
+struct Args {
+    input: std::path::PathBuf,
+    filters: Vec<String>,
+    skip: Vec<String>,
+    exact: bool,
+    status: Vec<CaseStatus>,
+    format: OutputFormat,
+    color: anstream::ColorChoice,
+}
+
+fn parse_args() -> lexarg::Result<Args> {
+    use lexarg::prelude::*;
+
+    let mut input = None;
+    let mut filters = Vec::new();
+    let mut skip = Vec::new();
+    let mut exact = false;
+    let mut status = Vec::new();
+    let mut format = OutputFormat::Pretty;
+    let mut color = anstream::ColorChoice::Auto;
+    let raw = std::env::args_os().collect::<Vec<_>>();
+    let mut parser = lexarg::Parser::new(&raw);
+    let bin_name = parser
+        .next_raw()
+        .expect("nothing parsed yet so no attached lingering")
+        .expect("always at least one");
+    let mut prev_arg = Value(bin_name);
+    while let Some(arg) = parser.next_arg() {
+        match arg {
+            Long("skip") => {
+                let filter = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("FILTER")))
+                    .string("FILTER")
+                    .within(arg)?;
+                skip.push(filter.to_owned());
+            }
+            Long("exact") => {
+                exact = true;
+            }
+            Long("status") => {
+                let value = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("STATUS")))
+                    .one_of(&["passed", "failed", "ignored"])
+                    .within(arg)?;
+                status.push(match value {
+                    "passed" => CaseStatus::Passed,
+                    "failed" => CaseStatus::Failed,
+                    "ignored" => CaseStatus::Ignored,
+                    _ => unreachable!("`one_of` should prevent this"),
+                });
+            }
+            Long("format") => {
+                let value = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("FORMAT")))
+                    .one_of(&["pretty", "terse"])
+                    .within(arg)?;
+                format = match value {
+                    "pretty" => OutputFormat::Pretty,
+                    "terse" => OutputFormat::Terse,
+                    _ => unreachable!("`one_of` should prevent this"),
+                };
+            }
+            Short("q") | Long("quiet") => {
+                format = OutputFormat::Terse;
+            }
+            Long("color") => {
+                let value = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("WHEN")))
+                    .one_of(&["auto", "always", "never"])
+                    .within(arg)?;
+                color = match value {
+                    "auto" => anstream::ColorChoice::Auto,
+                    "always" => anstream::ColorChoice::Always,
+                    "never" => anstream::ColorChoice::Never,
+                    _ => unreachable!("`one_of` should prevent this"),
+                };
+            }
+            Short("h") | Long("help") => {
+                let help = lexarg::Help::new("libtest2-render").specs(OPTIONS);
+                print!("{help}");
+                std::process::exit(0);
+            }
+            // All values are the same, whether escaped or not, so its a no-op
+            Escape(_) => {}
+            Value(path) if input.is_none() => {
+                input = Some(path.path()?.to_owned());
+            }
+            Value(filter) => {
+                let filter = filter.string("FILTER")?;
+                filters.push(filter.to_owned());
+            }
+            Unexpected(_) => {
+                return Err(LexError::msg("unexpected value")
+                    .unexpected(arg)
+                    .within(prev_arg)
+                    .into());
+            }
+            _ => {
+                let error = LexError::msg("unexpected argument").unexpected(arg);
+                return Err(lexarg::suggest_long(error, arg, OPTIONS).into());
+            }
+        }
+        prev_arg = arg;
+    }
+
+    Ok(Args {
+        input: input
+            .ok_or_missing(Value(std::ffi::OsStr::new("FILE")))
+            .within(Value(bin_name))?,
+        filters,
+        skip,
+        exact,
+        status,
+        format,
+        color,
+    })
+}
+
+fn read_events(input: &std::path::Path) -> Result<Vec<Event>, String> {
+    let content = if input == std::path::Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| format!("-: {e}"))?
+    } else {
+        std::fs::read_to_string(input).map_err(|e| format!("{}: {e}", input.display()))?
+    };
+
+    let mut events = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str::<Event>(line)
+            .map_err(|e| format!("{}:{}: {e}", input.display(), i + 1))?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// Hide cases the user is not interested in
+///
+/// Cases that do not match are reported as filtered out.
+fn filter_events(events: Vec<Event>, args: &Args) -> Vec<Event> {
+    let report = events.iter().cloned().collect::<RunReport>();
+    let is_shown = |name: &str| {
+        let matches_filter = |filter: &String| {
+            if args.exact {
+                name == filter
+            } else {
+                name.contains(filter.as_str())
+            }
+        };
+        if args.skip.iter().any(matches_filter) {
+            return false;
+        }
+        if !args.filters.is_empty() && !args.filters.iter().any(matches_filter) {
+            return false;
+        }
+        if !args.status.is_empty() {
+            let status = report.case(name).map(|c| c.status());
+            if !status.is_some_and(|s| args.status.contains(&s)) {
+                return false;
+            }
+        }
+        true
+    };
+
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::DiscoverCase(mut inner) => {
+                inner.selected &= is_shown(&inner.name);
+                Some(inner.into())
+            }
+            Event::CaseStart(ref inner) if !is_shown(&inner.name) => None,
+            Event::CaseMessage(ref inner) if !is_shown(&inner.name) => None,
+            Event::CaseOutput(ref inner) if !is_shown(&inner.name) => None,
+            Event::CaseAttachment(ref inner) if !is_shown(&inner.name) => None,
+            Event::CaseComplete(ref inner) if !is_shown(&inner.name) => None,
+            Event::Extension(ref inner)
+                if inner.name.as_deref().is_some_and(|name| !is_shown(name)) =>
+            {
+                None
+            }
+            event => Some(event),
+        })
+        .collect()
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
+    let events = match read_events(&args.input) {
+        Ok(events) => events,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
+    let events = filter_events(events, &args);
+
+    let stdout = anstream::AutoStream::new(std::io::stdout(), args.color);
+    if let Err(err) = libtest2_harness::replay(events, args.format, stdout) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
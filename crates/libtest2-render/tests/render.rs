@@ -0,0 +1,124 @@
+use snapbox::str;
+
+fn cmd() -> snapbox::cmd::Command {
+    snapbox::cmd::Command::new(snapbox::cmd::cargo_bin!("libtest2-render"))
+}
+
+const MIXED: &str = "tests/fixtures/mixed.jsonl";
+
+#[test]
+fn pretty() {
+    cmd().arg(MIXED).assert().success().stdout_eq(str![[r#"
+
+running 3 tests
+test passed  ... ok
+test failed  ... FAILED
+test ignored ... ignored
+
+failures:
+
+---- failed stdout ----
+hello world
+
+---- failed ----
+was not a good boy
+
+
+failures:
+    failed
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 1 filtered out; finished in 0.004s
+
+
+"#]]);
+}
+
+#[test]
+fn terse_stdin() {
+    cmd()
+        .args(["--quiet", "-"])
+        .stdin(std::fs::read_to_string(MIXED).unwrap())
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 3 tests
+.Fi
+failures:
+
+---- failed stdout ----
+hello world
+
+---- failed ----
+was not a good boy
+
+
+failures:
+    failed
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 1 filtered out; finished in 0.004s
+
+
+"#]]);
+}
+
+#[test]
+fn filter_name() {
+    cmd()
+        .args([MIXED, "passed", "ignored"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test passed  ... ok
+test ignored ... ignored
+
+test result: ok. 1 passed; 0 failed; 1 ignored; 2 filtered out; finished in 0.004s
+
+
+"#]]);
+}
+
+#[test]
+fn filter_status() {
+    cmd()
+        .args(["--status", "failed", MIXED])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test failed ... FAILED
+
+failures:
+
+---- failed stdout ----
+hello world
+
+---- failed ----
+was not a good boy
+
+
+failures:
+    failed
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 3 filtered out; finished in 0.004s
+
+
+"#]]);
+}
+
+#[test]
+fn invalid_event() {
+    let input = "{\"event\":\"run_start\"}\n{\"event\":\"bogus\"}\n";
+    cmd()
+        .arg("-")
+        .stdin(input)
+        .assert()
+        .code(1)
+        .stderr_eq(str![[r#"
+-:2: unknown variant `bogus`, expected one of [..] at line 1 column 16
+
+"#]]);
+}
@@ -0,0 +1,94 @@
+//! Measure code with `#[libtest2::bench]`, see [`Bencher`]
+
+use std::time::Duration;
+use std::time::Instant;
+
+use libtest2_harness::RunMode;
+
+use crate::TestContext;
+
+/// Time a routine, passed to a `#[libtest2::bench]` function
+///
+/// ```no_run
+/// use libtest2::Bencher;
+///
+/// #[libtest2::bench]
+/// fn sum(b: &mut Bencher) {
+///     b.iter(|| (0..1000).sum::<u64>());
+/// }
+/// #
+/// # #[libtest2::main]
+/// # fn main() {
+/// # }
+/// ```
+///
+/// With `--bench`, results are reported through [`TestContext::notify_bench`] and plain tests are
+/// ignored.  Otherwise, the routine is run once to check that it works.
+#[derive(Debug)]
+pub struct Bencher {
+    mode: RunMode,
+    summary: Option<Summary>,
+}
+
+impl Bencher {
+    /// Measure `routine`, passing its result to [`std::hint::black_box`]
+    pub fn iter<T>(&mut self, mut routine: impl FnMut() -> T) {
+        match self.mode {
+            RunMode::Test => {
+                std::hint::black_box(routine());
+            }
+            RunMode::Bench => {
+                self.summary = Some(measure(&mut routine));
+            }
+        }
+    }
+}
+
+/// Run a benchmark function, reporting what it measured
+pub fn run_bench<R>(context: &TestContext, bench: impl FnOnce(&mut Bencher) -> R) -> R {
+    let mut bencher = Bencher {
+        mode: context.current_mode(),
+        summary: None,
+    };
+    let result = bench(&mut bencher);
+    if let Some(summary) = bencher.summary {
+        // Failing to report isn't a failure of the benchmark
+        let _ = context.notify_bench(summary.median_ns, summary.deviation_ns);
+    }
+    result
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Summary {
+    median_ns: u128,
+    /// Spread between the fastest and slowest quarter of samples
+    deviation_ns: u128,
+}
+
+const SAMPLES: usize = 50;
+const SAMPLE_TARGET: Duration = Duration::from_millis(1);
+
+fn measure<T>(routine: &mut impl FnMut() -> T) -> Summary {
+    // Batch enough iterations that timer resolution doesn't dominate
+    let mut iterations = 1_u32;
+    while time(routine, iterations) < SAMPLE_TARGET && iterations < u32::MAX / 2 {
+        iterations *= 2;
+    }
+
+    let mut samples = (0..SAMPLES)
+        .map(|_| time(routine, iterations).as_nanos() / u128::from(iterations))
+        .collect::<Vec<_>>();
+    samples.sort_unstable();
+    Summary {
+        median_ns: samples[SAMPLES / 2],
+        deviation_ns: samples[SAMPLES * 3 / 4] - samples[SAMPLES / 4],
+    }
+}
+
+fn time<T>(routine: &mut impl FnMut() -> T, iterations: u32) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(routine());
+    }
+    start.elapsed()
+}
@@ -1,7 +1,10 @@
 use libtest2_harness::Case;
+use libtest2_harness::Hooks;
+use libtest2_harness::RunMode;
 use libtest2_harness::Source;
 use libtest2_harness::TestKind;
 
+use crate::RunError;
 use crate::RunResult;
 use crate::TestContext;
 
@@ -12,6 +15,9 @@ impl Case for DynCase {
     fn name(&self) -> &str {
         self.0.name()
     }
+    fn mode(&self) -> RunMode {
+        self.0.mode()
+    }
     fn kind(&self) -> TestKind {
         self.0.kind()
     }
@@ -21,6 +27,9 @@ impl Case for DynCase {
     fn exclusive(&self, context: &TestContext) -> bool {
         self.0.exclusive(context)
     }
+    fn tags(&self) -> &[String] {
+        self.0.tags()
+    }
 
     fn run(&self, context: &TestContext) -> RunResult {
         self.0.run(context)
@@ -29,6 +38,7 @@ impl Case for DynCase {
 
 pub struct FnCase<R> {
     name: String,
+    tags: Vec<String>,
     runner: R,
 }
 
@@ -39,9 +49,16 @@ where
     pub fn test(name: impl Into<String>, runner: R) -> Self {
         Self {
             name: name.into(),
+            tags: Vec::new(),
             runner,
         }
     }
+
+    /// Label the case for `--include-tag` and `--exclude-tag`
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
 }
 
 impl<R> Case for FnCase<R>
@@ -60,13 +77,32 @@ where
     fn exclusive(&self, _: &TestContext) -> bool {
         false
     }
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
 
     fn run(&self, context: &TestContext) -> RunResult {
         (self.runner)(context)
     }
 }
 
+/// Infer the [`TestKind`] from the environment Cargo compiles the test with
+///
+/// Cargo only sets `CARGO_TARGET_TMPDIR` for integration tests and benchmarks.
+pub const fn test_kind(cargo: Option<&str>, target_tmpdir: Option<&str>) -> TestKind {
+    match (cargo, target_tmpdir) {
+        (None, _) => TestKind::Unknown,
+        (Some(_), Some(_)) => TestKind::IntegrationTest,
+        (Some(_), None) => TestKind::UnitTest,
+    }
+}
+
 pub fn main(cases: impl IntoIterator<Item = impl Case + 'static>) {
+    main_with_hooks(cases, ());
+}
+
+/// Like [`main`], calling `hooks` around the tests
+pub fn main_with_hooks(cases: impl IntoIterator<Item = impl Case + 'static>, hooks: impl Hooks) {
     let harness = libtest2_harness::Harness::new();
     let harness = match harness.with_env() {
         Ok(harness) => harness,
@@ -78,7 +114,7 @@ pub fn main(cases: impl IntoIterator<Item = impl Case + 'static>) {
     let harness = match harness.parse() {
         Ok(harness) => harness,
         Err(err) => {
-            eprintln!("{err}");
+            eprint!("{}", harness.render_error(&err));
             ::std::process::exit(1);
         }
     };
@@ -91,12 +127,39 @@ pub fn main(cases: impl IntoIterator<Item = impl Case + 'static>) {
             ::std::process::exit(libtest2_harness::ERROR_EXIT_CODE)
         }
     };
-    match harness.run() {
+    match harness.run_with_hooks(FixtureHooks(hooks)) {
         Ok(true) => ::std::process::exit(0),
-        Ok(false) => ::std::process::exit(libtest2_harness::ERROR_EXIT_CODE),
+        Ok(_) => ::std::process::exit(libtest2_harness::ERROR_EXIT_CODE),
         Err(err) => {
             eprintln!("{err}");
             ::std::process::exit(libtest2_harness::ERROR_EXIT_CODE)
         }
     }
 }
+
+/// Tears down [`Scope::Run`][crate::Scope::Run] fixtures along with the user's hooks
+struct FixtureHooks<H>(H);
+
+impl<H: Hooks> Hooks for FixtureHooks<H> {
+    fn before_all(&self) -> RunResult {
+        self.0.before_all()
+    }
+
+    fn after_all(&self) -> RunResult {
+        self.0.after_all()
+    }
+
+    fn before_each(&self, context: &TestContext) -> RunResult {
+        self.0.before_each(context)
+    }
+
+    fn after_each(&self, context: &TestContext) -> RunResult {
+        self.0.after_each(context)
+    }
+
+    fn teardown_fixtures(&self) -> Vec<RunError> {
+        let mut errors = crate::fixture::teardown_run();
+        errors.extend(self.0.teardown_fixtures());
+        errors
+    }
+}
@@ -0,0 +1,53 @@
+//! Run `async fn` tests to completion
+//!
+//! An executor is any function with the signature of [`block_on`].  It is chosen for all tests
+//! with `#[libtest2::main(executor = path)]` and for one test with `#[executor(path)]`, defaulting
+//! to [`block_on`].
+
+use std::future::Future;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+
+/// Minimal executor, polling on the current thread
+///
+/// This is enough for futures that don't depend on a runtime, like for channels or
+/// `std::thread`-backed work.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            // Spurious wake ups just mean an extra poll
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+struct ThreadWaker(std::thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run on a new single-threaded [`tokio`](https://docs.rs/tokio) runtime
+///
+/// Each test gets its own runtime, with all drivers enabled by your `tokio` features.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub fn tokio<F: Future>(future: F) -> F::Output {
+    ::tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can create a runtime")
+        .block_on(future)
+}
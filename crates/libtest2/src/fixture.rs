@@ -0,0 +1,266 @@
+//! Set up what a test depends on, see [`Fixture`]
+
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use crate::RunError;
+use crate::RunResult;
+use crate::TestContext;
+
+/// Provide a test parameter of this type, with setup and teardown
+///
+/// A `#[test]` taking `&T` after the [`TestContext`] gets `T` from its `Fixture` impl:
+///
+/// ```no_run
+/// # use libtest2::RunError;
+/// # use libtest2::RunResult;
+/// # use libtest2::TestContext;
+/// use libtest2::Fixture;
+/// use libtest2::Scope;
+///
+/// struct Database(String);
+///
+/// impl Fixture for Database {
+///     const SCOPE: Scope = Scope::Run;
+///
+///     fn setup(_context: &TestContext) -> Result<Self, RunError> {
+///         Ok(Database("postgres://localhost".to_owned()))
+///     }
+/// }
+///
+/// #[libtest2::test]
+/// fn query(_context: &TestContext, db: &Database) {
+///     assert!(db.0.starts_with("postgres"));
+/// }
+/// #
+/// # #[libtest2::main]
+/// # fn main() {
+/// # }
+/// ```
+///
+/// Fixtures are set up in parameter order and torn down in reverse.  A failure in either is
+/// reported separately from the test failing.
+pub trait Fixture: Sized + Send + Sync + 'static {
+    /// How long a value is shared for
+    const SCOPE: Scope = Scope::Test;
+
+    /// Create the value
+    ///
+    /// For [`Scope::Run`], this is called with the context of the first test that needs it.
+    fn setup(context: &TestContext) -> Result<Self, RunError>;
+
+    /// Clean up the value when its [`Scope`] ends
+    ///
+    /// This is also called if the test panics.
+    fn teardown(self) -> RunResult {
+        Ok(())
+    }
+}
+
+/// How long a [`Fixture`] lives
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Set up for each test that uses it
+    Test,
+    /// Set up once, by the first test that uses it, and torn down after all tests have run
+    ///
+    /// A failed setup is reported for every test that uses it.
+    Run,
+}
+
+/// A test function, whose parameters after the [`TestContext`] are [`Fixture`]s
+///
+/// `Args` distinguishes the implementations by parameter types.
+pub trait TestFn<Args> {
+    type Output;
+
+    /// Set up the fixtures, call the test through `wrap`, and tear the fixtures down
+    ///
+    /// The result of tearing down is returned separately to not hide a test failure.
+    fn run<O>(
+        &self,
+        context: &TestContext,
+        wrap: impl FnOnce(&dyn Fn() -> Self::Output) -> O,
+    ) -> Result<(O, RunResult), RunError>;
+}
+
+/// Marks test functions that don't take a [`TestContext`]
+pub struct NoContext;
+
+impl<F, R> TestFn<NoContext> for F
+where
+    F: Fn() -> R,
+{
+    type Output = R;
+
+    fn run<O>(
+        &self,
+        _context: &TestContext,
+        wrap: impl FnOnce(&dyn Fn() -> Self::Output) -> O,
+    ) -> Result<(O, RunResult), RunError> {
+        Ok((wrap(self), Ok(())))
+    }
+}
+
+macro_rules! impl_test_fn {
+    ($($fixture:ident $value:ident),* ; $($rev:ident),*) => {
+        impl<F, R, $($fixture),*> TestFn<($($fixture,)*)> for F
+        where
+            F: Fn(&TestContext, $(&$fixture),*) -> R,
+            $($fixture: Fixture,)*
+        {
+            type Output = R;
+
+            fn run<O>(
+                &self,
+                context: &TestContext,
+                wrap: impl FnOnce(&dyn Fn() -> Self::Output) -> O,
+            ) -> Result<(O, RunResult), RunError> {
+                // Earlier fixtures are torn down on drop if a later one fails
+                $(let $value = Held::<$fixture>::setup(context)?;)*
+
+                let output = wrap(&|| self(context, $($value.get()),*));
+
+                #[allow(unused_mut)]
+                let mut teardown = Ok(());
+                $(teardown = teardown.and($rev.teardown());)*
+                Ok((output, teardown))
+            }
+        }
+    };
+}
+
+impl_test_fn!(;);
+impl_test_fn!(A0 a0; a0);
+impl_test_fn!(A0 a0, A1 a1; a1, a0);
+impl_test_fn!(A0 a0, A1 a1, A2 a2; a2, a1, a0);
+impl_test_fn!(A0 a0, A1 a1, A2 a2, A3 a3; a3, a2, a1, a0);
+impl_test_fn!(A0 a0, A1 a1, A2 a2, A3 a3, A4 a4; a4, a3, a2, a1, a0);
+impl_test_fn!(A0 a0, A1 a1, A2 a2, A3 a3, A4 a4, A5 a5; a5, a4, a3, a2, a1, a0);
+
+/// A [`Fixture`] value held by a test
+pub struct Held<T: Fixture> {
+    value: Option<Value<T>>,
+}
+
+enum Value<T> {
+    Owned(T),
+    Shared(Arc<T>),
+}
+
+impl<T: Fixture> Held<T> {
+    fn setup(context: &TestContext) -> Result<Self, RunError> {
+        let value = match T::SCOPE {
+            Scope::Test => T::setup(context)
+                .map(Value::Owned)
+                .map_err(|err| setup_failed::<T>(&err))?,
+            Scope::Run => Value::Shared(shared::<T>(context)?),
+        };
+        Ok(Self { value: Some(value) })
+    }
+
+    fn get(&self) -> &T {
+        match self.value.as_ref().expect("only taken on teardown") {
+            Value::Owned(value) => value,
+            Value::Shared(value) => value,
+        }
+    }
+
+    fn teardown(mut self) -> RunResult {
+        match self.value.take() {
+            Some(Value::Owned(value)) => value.teardown().map_err(|err| teardown_failed::<T>(&err)),
+            Some(Value::Shared(_)) | None => Ok(()),
+        }
+    }
+}
+
+impl<T: Fixture> Drop for Held<T> {
+    fn drop(&mut self) {
+        // Not explicitly torn down because setup or the test failed
+        if let Some(Value::Owned(value)) = self.value.take() {
+            let _ = value.teardown();
+        }
+    }
+}
+
+type Cell = Arc<OnceLock<Result<Arc<dyn Any + Send + Sync>, String>>>;
+
+struct Shared {
+    type_id: std::any::TypeId,
+    cell: Cell,
+    teardown: fn(Arc<dyn Any + Send + Sync>) -> RunResult,
+}
+
+/// [`Scope::Run`] fixtures, in the order they were first requested
+static SHARED: Mutex<Vec<Shared>> = Mutex::new(Vec::new());
+
+fn shared<T: Fixture>(context: &TestContext) -> Result<Arc<T>, RunError> {
+    let cell = {
+        let mut shared = SHARED
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let type_id = std::any::TypeId::of::<T>();
+        match shared.iter().find(|s| s.type_id == type_id) {
+            Some(s) => s.cell.clone(),
+            None => {
+                let cell = Cell::default();
+                shared.push(Shared {
+                    type_id,
+                    cell: cell.clone(),
+                    teardown: |value| match value.downcast::<T>().map(Arc::try_unwrap) {
+                        Ok(Ok(value)) => value.teardown().map_err(|err| teardown_failed::<T>(&err)),
+                        // Leaked by a test
+                        Ok(Err(_)) | Err(_) => Ok(()),
+                    },
+                });
+                cell
+            }
+        }
+    };
+    // Setup happens outside of the lock so fixtures of other types aren't blocked
+    let value = cell.get_or_init(|| {
+        T::setup(context)
+            .map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>)
+            .map_err(|err| setup_failed::<T>(&err).to_string())
+    });
+    match value {
+        Ok(value) => Ok(value
+            .clone()
+            .downcast::<T>()
+            .expect("cells are looked up by `TypeId`")),
+        Err(err) => Err(RunError::fixture(err)),
+    }
+}
+
+/// Tear down [`Scope::Run`] fixtures in reverse order, after all tests have run
+pub(crate) fn teardown_run() -> Vec<RunError> {
+    let shared = std::mem::take(
+        &mut *SHARED
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    );
+    shared
+        .into_iter()
+        .rev()
+        .filter_map(|shared| {
+            let value = Arc::into_inner(shared.cell)?.into_inner()?.ok()?;
+            (shared.teardown)(value).err()
+        })
+        .collect()
+}
+
+fn setup_failed<T>(err: &RunError) -> RunError {
+    RunError::fixture(format_args!(
+        "fixture `{}` failed to set up: {err}",
+        std::any::type_name::<T>()
+    ))
+}
+
+fn teardown_failed<T>(err: &RunError) -> RunError {
+    RunError::fixture(format_args!(
+        "fixture `{}` failed to tear down: {err}",
+        std::any::type_name::<T>()
+    ))
+}
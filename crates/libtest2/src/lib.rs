@@ -28,6 +28,146 @@
 //! }
 //! ```
 //!
+//! ## Parameterized tests
+//!
+//! `#[case(..)]` registers a test per row, passing the values after the [`TestContext`].
+//! Rows are named by position, like `add_one::case_1`, or by label with `#[case::label(..)]`, so
+//! they can be filtered, skipped and reported individually:
+//!
+//! ```no_run
+//! # use libtest2::TestContext;
+//! #[libtest2::test]
+//! #[case(1, 2)]
+//! #[case::negative(-1, 0)]
+//! fn add_one(_context: &TestContext, input: i32, expected: i32) {
+//!     assert_eq!(input + 1, expected);
+//! }
+//! #
+//! # #[libtest2::main]
+//! # fn main() {
+//! # }
+//! ```
+//!
+//! Like `#[ignore]`, `#[case]` must come after the `#[test]` macro.  It isn't supported on an
+//! `async fn`.
+//!
+//! ## Fixtures
+//!
+//! Parameters after the [`TestContext`] are looked up by type, calling their [`Fixture`] impl to
+//! set them up for the test and tear them down afterwards.  A [`Scope::Run`] fixture is shared
+//! between tests, being set up by whichever needs it first and torn down after all tests have
+//! run.
+//!
+//! Fixtures can't be combined with `#[case]`.
+//!
+//! ## Randomized tests
+//!
+//! [`TestContext::rng`] gives each test a random number generator, seeded from the run's seed
+//! and the test's name.  If the test fails, the seed is reported along with how to reproduce
+//! the failure with `--seed`:
+//!
+//! ```no_run
+//! # use libtest2::TestContext;
+//! #[libtest2::test]
+//! fn round_trip(context: &TestContext) {
+//!     let mut rng = context.rng();
+//!     for _ in 0..100 {
+//!         let value = rng.next_u64();
+//!         assert_eq!(value.to_string().parse::<u64>().unwrap(), value);
+//!     }
+//! }
+//! #
+//! # #[libtest2::main]
+//! # fn main() {
+//! # }
+//! ```
+//!
+//! ## Setup and teardown
+//!
+//! `#[libtest2::main(before_all = path, after_all = path)]` runs code once around all of the
+//! tests, like starting and stopping a server they share.  `before_each` and `after_each` are
+//! called with the [`TestContext`] around each test.  Hooks return anything implementing
+//! [`IntoRunResult`]:
+//!
+//! ```no_run
+//! # use libtest2::RunResult;
+//! # use libtest2::TestContext;
+//! fn start_server() -> RunResult {
+//!     Ok(())
+//! }
+//!
+//! fn stop_server() {
+//! }
+//!
+//! #[libtest2::main(before_all = start_server, after_all = stop_server)]
+//! fn main() {
+//! }
+//! ```
+//!
+//! If `before_all` fails, no tests are run, though `after_all` is still called.  A failing hook
+//! fails the run.  Hooks are skipped with `--list`.
+//!
+//! ## Async tests
+//!
+//! An `async fn` test is run to completion on an [executor], [`executor::block_on`] by default.
+//! Pick another for all tests with `#[libtest2::main(executor = path)]`, or for one test with
+//! `#[executor(path)]`:
+//!
+//! ```no_run
+//! # use libtest2::TestContext;
+//! #[libtest2::test]
+//! #[executor(libtest2::executor::block_on)]
+//! async fn check_toph(_context: &TestContext) {
+//!     std::future::ready(()).await;
+//! }
+//!
+//! #[libtest2::main(executor = libtest2::executor::block_on)]
+//! fn main() {
+//! }
+//! ```
+//!
+//! With the `tokio` feature, [`executor::tokio`] runs each test on its own runtime.
+//!
+//! ## Exclusive tests
+//!
+//! `#[exclusive]`, or its alias `#[serial]`, runs the test while no other tests are, like for
+//! tests that change environment variables or the current directory.  To only sometimes be
+//! exclusive, pass a condition that is called with the [`TestContext`]:
+//!
+//! ```no_run
+//! # use libtest2::TestContext;
+//! #[libtest2::test]
+//! #[exclusive(|_: &TestContext| std::env::var_os("CI").is_some())]
+//! fn check_toph(_context: &TestContext) {
+//! }
+//! #
+//! # #[libtest2::main]
+//! # fn main() {
+//! # }
+//! ```
+//!
+//! ## Tags
+//!
+//! `#[tags(..)]` labels a test so runs can be sliced with `--include-tag` and `--exclude-tag`,
+//! like `--exclude-tag network` or `--include-tag network+slow` for tests with both tags:
+//!
+//! ```no_run
+//! # use libtest2::TestContext;
+//! #[libtest2::test]
+//! #[tags("network", "slow")]
+//! fn check_toph(_context: &TestContext) {
+//! }
+//! #
+//! # #[libtest2::main]
+//! # fn main() {
+//! # }
+//! ```
+//!
+//! ## Benchmarks
+//!
+//! [`#[bench]`][bench] functions take a [`Bencher`] and are listed as `bench` rather than `test`.
+//! They are measured with `--bench` and run once with `--test`, checking that they still work.
+//!
 //! # Known limitations and differences to the official test harness
 //!
 //! `libtest2` aims to be fully compatible with stable, non-deprecated parts of `libtest`
@@ -35,12 +175,12 @@
 //!
 //! Some of the notable differences:
 //!
-//! - `#[test]` does not support all `Termination` types as return values,
-//!   only what [`IntoRunResult`] supports.
+//! - `#[test]` return types must implement [`IntoRunResult`] rather than `Termination`, though
+//!   the same types are supported.
 //! - `#[ignore]` must come after the `#[test]` macro
 //! - `#[should_ignore]` must come after the `#[test]` macro.
 //!   The error output if the test fails to panic is also different from `libtest`.
-//! - Output capture and `--no-capture`: simply not supported. The official
+//! - Output capture: simply not supported, so `--no-capture` is always in effect. The official
 //!   `libtest` uses internal `std` functions to temporarily redirect output.
 //!   `libtest` cannot use those, see also [libtest2#12](https://github.com/assert-rs/libtest2/issues/12)
 //! - `--format=json` (unstable): our schema is part of an experiment to see what should be
@@ -50,33 +190,64 @@
 //#![warn(clippy::print_stderr)]
 #![warn(clippy::print_stdout)]
 
+mod bench;
 mod case;
+mod fixture;
 mod macros;
+mod result;
 
+pub mod executor;
 pub mod panic;
 
 #[doc(hidden)]
 pub mod _private {
     pub use distributed_list::push;
     pub use distributed_list::DistributedList;
+    pub use libtest2_harness::RunMode;
     pub use libtest2_harness::Source;
     pub use libtest2_harness::TestKind;
 
+    pub use crate::_into_run_result as into_run_result;
+    pub use crate::_main_hook as main_hook;
     pub use crate::_main_parse as main_parse;
+    pub use crate::_parse_exclusive as parse_exclusive;
     pub use crate::_parse_ignore as parse_ignore;
     pub use crate::_run_test as run_test;
-    pub use crate::_test_expr as test_expr;
+    pub use crate::_test_cases as test_cases;
+    pub use crate::_test_fn as test_fn;
+    pub use crate::_test_parse as bench_parse;
     pub use crate::_test_parse as test_parse;
+    pub use crate::_test_source as test_source;
+    pub use crate::_test_tags as test_tags;
+    pub use crate::bench::run_bench;
+    pub use crate::case::test_kind;
     pub use crate::case::DynCase;
+    pub use crate::fixture::Held;
+    pub use crate::fixture::NoContext;
+    pub use crate::fixture::TestFn;
+    pub use crate::result::ErrorKind;
+    pub use crate::result::ErrorResult;
+    pub use crate::result::ErrorTag;
+    pub use crate::result::OtherKind;
+    pub use crate::result::OtherTag;
 }
 
+pub use bench::Bencher;
 pub use case::main;
+pub use case::main_with_hooks;
 pub use case::FnCase;
+pub use fixture::Fixture;
+pub use fixture::Scope;
 pub use libtest2_harness::Case;
+pub use libtest2_harness::Hooks;
 pub use libtest2_harness::IntoRunResult;
+pub use libtest2_harness::Rng;
 pub use libtest2_harness::RunError;
 pub use libtest2_harness::RunResult;
+pub use libtest2_harness::Source;
 pub use libtest2_harness::TestContext;
+pub use libtest2_harness::TestKind;
+pub use libtest2_proc_macro::bench;
 pub use libtest2_proc_macro::main;
 pub use libtest2_proc_macro::test;
 
@@ -2,14 +2,85 @@
 #[doc(hidden)]
 macro_rules! _main_parse {
     (#[main] $(#[$meta:meta])* fn main $($item:tt)*) => {
+        $crate::_private::main_parse!(#[main()] $(#[$meta])* fn main $($item)*);
+    };
+    (#[main($($args:tt)*)] $(#[$meta:meta])* fn main $($item:tt)*) => {
+        $crate::_private::main_parse!(
+            args=[$($args)*]
+            executor=[]
+            before_all=[]
+            after_all=[]
+            before_each=[]
+            after_each=[]
+            item=[$(#[$meta])* fn main $($item)*]
+        );
+    };
+
+    // Process `#[main(key = value, ..)]`, one argument at a time
+    (args=[executor = $value:expr $(, $($args:tt)*)?] executor=[] before_all=$before_all:tt after_all=$after_all:tt before_each=$before_each:tt after_each=$after_each:tt item=$item:tt) => {
+        $crate::_private::main_parse!(args=[$($($args)*)?] executor=[$value] before_all=$before_all after_all=$after_all before_each=$before_each after_each=$after_each item=$item);
+    };
+    (args=[before_all = $value:expr $(, $($args:tt)*)?] executor=$executor:tt before_all=[] after_all=$after_all:tt before_each=$before_each:tt after_each=$after_each:tt item=$item:tt) => {
+        $crate::_private::main_parse!(args=[$($($args)*)?] executor=$executor before_all=[$value] after_all=$after_all before_each=$before_each after_each=$after_each item=$item);
+    };
+    (args=[after_all = $value:expr $(, $($args:tt)*)?] executor=$executor:tt before_all=$before_all:tt after_all=[] before_each=$before_each:tt after_each=$after_each:tt item=$item:tt) => {
+        $crate::_private::main_parse!(args=[$($($args)*)?] executor=$executor before_all=$before_all after_all=[$value] before_each=$before_each after_each=$after_each item=$item);
+    };
+    (args=[before_each = $value:expr $(, $($args:tt)*)?] executor=$executor:tt before_all=$before_all:tt after_all=$after_all:tt before_each=[] after_each=$after_each:tt item=$item:tt) => {
+        $crate::_private::main_parse!(args=[$($($args)*)?] executor=$executor before_all=$before_all after_all=$after_all before_each=[$value] after_each=$after_each item=$item);
+    };
+    (args=[after_each = $value:expr $(, $($args:tt)*)?] executor=$executor:tt before_all=$before_all:tt after_all=$after_all:tt before_each=$before_each:tt after_each=[] item=$item:tt) => {
+        $crate::_private::main_parse!(args=[$($($args)*)?] executor=$executor before_all=$before_all after_all=$after_all before_each=$before_each after_each=[$value] item=$item);
+    };
+    (args=[$key:ident $($args:tt)*] $($rest:tt)*) => {
+        compile_error!(concat!("unsupported or repeated `#[main]` argument `", stringify!($key), "`"));
+    };
+
+    // Generate the entry point
+    (args=[] executor=[] $($rest:tt)*) => {
+        $crate::_private::main_parse!(args=[] executor=[$crate::executor::block_on] $($rest)*);
+    };
+    (args=[] executor=[$executor:expr] before_all=$before_all:tt after_all=$after_all:tt before_each=$before_each:tt after_each=$after_each:tt item=[$(#[$meta:meta])* fn main $($item:tt)*]) => {
+        /// Default executor for `async fn` tests
+        #[allow(dead_code)]
+        fn __libtest2_block_on<F: ::core::future::Future>(future: F) -> F::Output {
+            ($executor)(future)
+        }
+
         static TESTS: $crate::_private::DistributedList<$crate::_private::DynCase> = $crate::_private::DistributedList::root();
 
+        /// Hooks from `#[main(before_all = .., ..)]`
+        struct __Libtest2Hooks;
+
+        impl $crate::Hooks for __Libtest2Hooks {
+            $crate::_private::main_hook!(before_all() $before_all);
+            $crate::_private::main_hook!(after_all() $after_all);
+            $crate::_private::main_hook!(before_each(context) $before_each);
+            $crate::_private::main_hook!(after_each(context) $after_each);
+        }
+
         $(#[$meta])*
         fn main() {
             fn inner $($item)*
 
             inner();
-            $crate::main(TESTS.iter().copied());
+            $crate::main_with_hooks(TESTS.iter().copied(), __Libtest2Hooks);
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _main_hook {
+    ($name:ident($($context:ident)?) []) => {};
+    ($name:ident() [$hook:expr]) => {
+        fn $name(&self) -> $crate::RunResult {
+            $crate::_private::into_run_result!(($hook)())
+        }
+    };
+    ($name:ident(context) [$hook:expr]) => {
+        fn $name(&self, context: &$crate::TestContext) -> $crate::RunResult {
+            $crate::_private::into_run_result!(($hook)(context))
         }
     };
 }
@@ -19,90 +90,331 @@ macro_rules! _main_parse {
 #[allow(clippy::crate_in_macro_def)] // accessing item defined by `_main_parse`/`_parse_ignore`/`_run_test`, and recursively calling the macro itself
 macro_rules! _test_parse {
     // Entry point
-    (#[test] $(#[$($attr:tt)+])* fn $name:ident $($item:tt)*) => {
+    (#[test] source=$source:tt $(#[$($attr:tt)+])* fn $name:ident $($item:tt)*) => {
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=Test
             body=[$($item)*]
             attrs=[$(#[$($attr)+])*]
+            cases=[]
+            executor=[]
+            exclusive=[]
+            tags=[]
+        );
+    };
+    (#[test] source=$source:tt $(#[$($attr:tt)+])* async fn $name:ident $($item:tt)*) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=Test
+            body=[$($item)*]
+            attrs=[$(#[$($attr)+])*]
+            cases=[]
+            executor=[crate::__libtest2_block_on]
+            exclusive=[]
+            tags=[]
+        );
+    };
+    // Benchmarks are tests that hand a `Bencher` to the user's function
+    (#[bench] source=$source:tt $(#[$($attr:tt)+])* fn $name:ident($($params:tt)*) $(-> $ret:ty)? $body:block) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=Bench
+            body=[(context: &$crate::TestContext) $(-> $ret)? {
+                fn bench($($params)*) $(-> $ret)? $body
+
+                $crate::_private::run_bench(context, bench)
+            }]
+            attrs=[$(#[$($attr)+])*]
+            cases=[]
+            executor=[]
+            exclusive=[]
+            tags=[]
         );
     };
 
     // Recursively handle attributes:
 
     // Edge condition (no more attributes to parse)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[] $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
         $crate::_private::test_parse!(break:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[case::label(..)]`, with `#[case(..)]` labeled by the proc-macro
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[case::$label:ident($($args:tt)*)] $(#[$($attr:tt)+])*] cases=[$($cases:tt)*] executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=[$($cases)* ($label [$($args)*])]
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[executor(..)]`, overriding the one from `#[main]`
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[executor($($executor:tt)+)] $(#[$($attr:tt)+])*] cases=$cases:tt executor=[$($default:tt)+] exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=[$($executor)+]
+            exclusive=$exclusive
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Emit an error for `#[executor(..)]` on a sync test (but continue parsing)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[executor $($unused:tt)*] $(#[$($attr:tt)+])*] cases=$cases:tt executor=[] exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        compile_error!("'executor' is only supported on an 'async fn'");
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=[]
+            exclusive=$exclusive
+            tags=$tags
             $(ignore=$ignore)?
             $(should_panic=$should_panic)?
         );
     };
     // Process `#[ignore]`/`#[ignore = ".."]` (NOTE: This will only match if an ignore macro has not already been parsed)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[ignore $(= $reason:literal)?] $(#[$($attr:tt)+])*] $(should_panic=$should_panic:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[ignore $(= $reason:literal)?] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(should_panic=$should_panic:tt)?) => {
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             ignore=[$($reason)?]
             $(should_panic=$should_panic)?
         );
     };
     // Ignore subsequent calls to `#[ignore]`/`#[ignore = ".."]`
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[ignore $(= $reason:literal)?] $(#[$($attr:tt)+])*] ignore=$ignore:tt $(should_panic=$should_panic:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[ignore $(= $reason:literal)?] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt ignore=$ignore:tt $(should_panic=$should_panic:tt)?) => {
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             ignore=$ignore
             $(should_panic=$should_panic)?
         );
     };
     // Process `#[should_panic]`/`#[should_panic = ".."]` (NOTE: This will only match if a should_panic macro has not already been parsed)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[should_panic $(= $expected:literal)?] $(#[$($attr:tt)+])*] $(ignore=$ignore:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[should_panic $(= $expected:literal)?] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)?) => {
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             $(ignore=$ignore)?
             should_panic=[$($expected)?]
         );
     };
     // Process `#[should_panic(expected = "..")]` (NOTE: Same as branch above)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[should_panic(expected = $expected:literal)] $(#[$($attr:tt)+])*] $(ignore=$ignore:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[should_panic(expected = $expected:literal)] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)?) => {
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             $(ignore=$ignore)?
             should_panic=[$expected]
         );
     };
     // Emit an error for subsequent calls to `#[should_panic]`/`#[should_panic = ".."]`/`#[should_panic(expected = "..")]` (but continue parsing)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[should_panic $($unused:tt)*] $(#[$($attr:tt)+])*] $(ignore=$ignore:tt)? should_panic=$should_panic:tt) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[should_panic $($unused:tt)*] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? should_panic=$should_panic:tt) => {
         compile_error!("annotating a test with multiple 'should_panic' attributes is not allowed");
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             $(ignore=$ignore)?
             should_panic=$should_panic
         );
     };
+    // Process `#[exclusive]`, running the test while no others are (NOTE: This will only match if neither `#[exclusive]` nor `#[serial]` have already been parsed)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[exclusive] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[|_: &$crate::TestContext| true]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[exclusive(condition)]`, with `condition` being called with the `TestContext` (NOTE: Same as branch above)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[exclusive($($condition:tt)+)] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[$($condition)+]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[serial]`, an alias for `#[exclusive]` (NOTE: Same as branch above)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[serial] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[|_: &$crate::TestContext| true]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[serial(condition)]`, with `condition` being called with the `TestContext` (NOTE: Same as branch above)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[serial($($condition:tt)+)] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[$($condition)+]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Emit an error for subsequent calls to `#[exclusive]`/`#[serial]` (but continue parsing)
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[exclusive $($unused:tt)*] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[$($exclusive:tt)+] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        compile_error!("annotating a test with multiple 'exclusive' or 'serial' attributes is not allowed");
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[$($exclusive)+]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[serial $($unused:tt)*] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=[$($exclusive:tt)+] tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        compile_error!("annotating a test with multiple 'exclusive' or 'serial' attributes is not allowed");
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=[$($exclusive)+]
+            tags=$tags
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
+    // Process `#[tags(..)]`, adding to any earlier ones
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[tags($($tag:literal),+ $(,)?)] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=[$($tags:tt)*] $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_parse!(continue:
+            name=$name
+            source=$source
+            mode=$mode
+            body=[$($item)*]
+            attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=[$($tags)* $($tag,)+]
+            $(ignore=$ignore)?
+            $(should_panic=$should_panic)?
+        );
+    };
     // Emit error on unknown attributes (but continue parsing)
-    (continue: name=$name:ident body=[$($item:tt)*] attrs=[#[$($unknown_attr:tt)+] $(#[$($attr:tt)+])*] $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+    (continue: name=$name:ident source=$source:tt mode=$mode:ident body=[$($item:tt)*] attrs=[#[$($unknown_attr:tt)+] $(#[$($attr:tt)+])*] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
         compile_error!(concat!("unknown attribute '", stringify!($($unknown_attr)+), "'"));
         $crate::_private::test_parse!(continue:
             name=$name
+            source=$source
+            mode=$mode
             body=[$($item)*]
             attrs=[$(#[$($attr)*])*]
+            cases=$cases
+            executor=$executor
+            exclusive=$exclusive
+            tags=$tags
             $(ignore=$ignore)?
             $(should_panic=$should_panic)?
         );
     };
 
     // End result
-    (break: name=$name:ident body=[($($params:tt)*) $($item:tt)*] $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+    (break: name=$name:ident source=$source:tt mode=$mode:ident body=[($($params:tt)*) $(-> $ret:ty)? $body:block] cases=[] executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
         #[allow(non_camel_case_types)]
         struct $name;
 
@@ -114,27 +426,211 @@ macro_rules! _test_parse {
                 let i = FULL_PATH.find("::").expect("we have inserted this in the line above so it must be there");
                 &FULL_PATH[(i+2)..]
             }
+            fn mode(&self) -> $crate::_private::RunMode {
+                $crate::_private::RunMode::$mode
+            }
             fn kind(&self) -> $crate::_private::TestKind {
-                Default::default()
+                $crate::_private::test_kind(option_env!("CARGO"), option_env!("CARGO_TARGET_TMPDIR"))
             }
             fn source(&self) -> Option<&$crate::_private::Source> {
-                None
+                $crate::_private::test_source!($source)
+            }
+            fn exclusive(&self, context: &$crate::TestContext) -> bool {
+                $crate::_private::parse_exclusive!(context, $exclusive)
             }
-            fn exclusive(&self, _: &$crate::TestContext) -> bool {
-                false
+            fn tags(&self) -> &[::std::string::String] {
+                $crate::_private::test_tags!($tags)
             }
 
             fn run(&self, context: &$crate::TestContext) -> $crate::RunResult {
-                fn run($($params)*) $($item)*
+                $crate::_private::test_fn!(executor=$executor fn run($($params)*) $(-> $ret)? $body);
 
                 $crate::_private::parse_ignore!(context, $($ignore)?);
 
-                use $crate::IntoRunResult;
-                let result = $crate::_private::run_test!($crate::_private::test_expr!(context, [$($params)*]), $($should_panic)?);
-                IntoRunResult::into_run_result(result)
+                let (result, teardown) = $crate::_private::TestFn::run(&run, context, |test_fn| {
+                    $crate::_private::run_test!(test_fn(), returns=[$($ret)?], $($should_panic)?)
+                })?;
+                result.and(teardown)
+            }
+        }
+    };
+    // End result for parameterized tests, registering a `Case` per `#[case]`
+    (break: name=$name:ident source=$source:tt mode=$mode:ident body=[($($params:tt)*) $(-> $ret:ty)? $body:block] cases=$cases:tt executor=$executor:tt exclusive=$exclusive:tt tags=$tags:tt $(ignore=$ignore:tt)? $(should_panic=$should_panic:tt)?) => {
+        $crate::_private::test_fn!(executor=$executor fn $name($($params)*) $(-> $ret)? $body);
+
+        $crate::_private::test_cases!(
+            name=$name
+            source=$source
+            mode=$mode
+            returns=[$($ret)?]
+            exclusive=$exclusive
+            tags=$tags
+            ignore=[$($ignore)?]
+            should_panic=[$($should_panic)?]
+            cases=$cases
+        );
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[allow(clippy::crate_in_macro_def)] // accessing item defined by `_main_parse`, and recursively calling the macro itself
+macro_rules! _test_cases {
+    (name=$name:ident source=$source:tt mode=$mode:ident returns=$returns:tt exclusive=$exclusive:tt tags=$tags:tt ignore=$ignore:tt should_panic=$should_panic:tt cases=[]) => {};
+    (name=$name:ident source=$source:tt mode=$mode:ident returns=$returns:tt exclusive=$exclusive:tt tags=$tags:tt ignore=[$($ignore:tt)?] should_panic=[$($should_panic:tt)?] cases=[($label:ident [$($args:tt)*]) $($cases:tt)*]) => {
+        const _: () = {
+            struct Row;
+
+            impl $crate::Case for Row {
+                fn name(&self) -> &str {
+                    $crate::_private::push!(crate::TESTS, _: $crate::_private::DynCase = $crate::_private::DynCase(&Row));
+
+                    const FULL_PATH: &str = concat!(std::module_path!(), "::", stringify!($name), "::", stringify!($label));
+                    let i = FULL_PATH.find("::").expect("we have inserted this in the line above so it must be there");
+                    &FULL_PATH[(i+2)..]
+                }
+                fn mode(&self) -> $crate::_private::RunMode {
+                    $crate::_private::RunMode::$mode
+                }
+                fn kind(&self) -> $crate::_private::TestKind {
+                    $crate::_private::test_kind(option_env!("CARGO"), option_env!("CARGO_TARGET_TMPDIR"))
+                }
+                fn source(&self) -> Option<&$crate::_private::Source> {
+                    $crate::_private::test_source!($source)
+                }
+                fn exclusive(&self, context: &$crate::TestContext) -> bool {
+                    $crate::_private::parse_exclusive!(context, $exclusive)
+                }
+                fn tags(&self) -> &[::std::string::String] {
+                    $crate::_private::test_tags!($tags)
+                }
+
+                fn run(&self, context: &$crate::TestContext) -> $crate::RunResult {
+                    $crate::_private::parse_ignore!(context, $($ignore)?);
+
+                    $crate::_private::run_test!($name(context, $($args)*), returns=$returns, $($should_panic)?)
+                }
             }
+        };
+
+        $crate::_private::test_cases!(
+            name=$name
+            source=$source
+            mode=$mode
+            returns=$returns
+            exclusive=$exclusive
+            tags=$tags
+            ignore=[$($ignore)?]
+            should_panic=[$($should_panic)?]
+            cases=[$($cases)*]
+        );
+    };
+}
+
+/// Where the test function is, from `file!()`, `line!()`, and `column!()` invocations spanned by
+/// `#[libtest2::test]` at the start and end of the function
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _test_source {
+    ([$file:expr, $start_line:expr, $start_col:expr, $end_line:expr, $end_col:expr]) => {{
+        static SOURCE: ::std::sync::OnceLock<$crate::_private::Source> =
+            ::std::sync::OnceLock::new();
+        Some(SOURCE.get_or_init(|| $crate::_private::Source::Rust {
+            source_file: ::std::path::PathBuf::from($file),
+            start_line: $start_line as usize,
+            start_col: $start_col as usize,
+            end_line: $end_line as usize,
+            end_col: $end_col as usize,
+        }))
+    }};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _test_tags {
+    ([]) => {
+        &[]
+    };
+    ([$($tag:literal,)+]) => {{
+        static TAGS: ::std::sync::OnceLock<::std::vec::Vec<::std::string::String>> = ::std::sync::OnceLock::new();
+        TAGS.get_or_init(|| ::std::vec![$(::std::string::String::from($tag)),+])
+    }};
+}
+
+/// Define the test function, wrapping an `async fn` in a sync one that runs it on `executor`
+///
+/// The sync wrapper forwards each parameter through a binding from its own recursion so the
+/// bindings are distinct without needing to generate names.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _test_fn {
+    (executor=[] fn $name:ident $($item:tt)*) => {
+        fn $name $($item)*
+    };
+    (executor=[$($executor:tt)+] fn $name:ident($($params:tt)*) $(-> $ret:ty)? $body:block) => {
+        $crate::_private::test_fn!(@param
+            executor=[$($executor)+]
+            name=$name
+            ret=[$($ret)?]
+            body=$body
+            inner=[]
+            outer=[]
+            args=[]
+            pat=[]
+            rest=[$($params)*]
+        );
+    };
+
+    // Recursively handle parameters:
+
+    // Edge condition (no more parameters)
+    (@param executor=[$($executor:tt)+] name=$name:ident ret=[$($ret:ty)?] body=$body:block inner=[$($inner:tt)*] outer=[$($outer:tt)*] args=[$($args:tt)*] pat=[] rest=[]) => {
+        fn $name($($outer)*) $(-> $ret)? {
+            async fn $name($($inner)*) $(-> $ret)? $body
+
+            ($($executor)+)($name($($args)*))
         }
     };
+    // End of a parameter's pattern
+    (@param executor=$executor:tt name=$name:ident ret=$ret:tt body=$body:block inner=[$($inner:tt)*] outer=[$($outer:tt)*] args=[$($args:tt)*] pat=[$($pat:tt)+] rest=[: $ty:ty $(, $($rest:tt)*)?]) => {
+        $crate::_private::test_fn!(@param
+            executor=$executor
+            name=$name
+            ret=$ret
+            body=$body
+            inner=[$($inner)* $($pat)+: $ty,]
+            outer=[$($outer)* arg: $ty,]
+            args=[$($args)* arg,]
+            pat=[]
+            rest=[$($($rest)*)?]
+        );
+    };
+    // Part of a parameter's pattern
+    (@param executor=$executor:tt name=$name:ident ret=$ret:tt body=$body:block inner=$inner:tt outer=$outer:tt args=$args:tt pat=[$($pat:tt)*] rest=[$next:tt $($rest:tt)*]) => {
+        $crate::_private::test_fn!(@param
+            executor=$executor
+            name=$name
+            ret=$ret
+            body=$body
+            inner=$inner
+            outer=$outer
+            args=$args
+            pat=[$($pat)* $next]
+            rest=[$($rest)*]
+        );
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _parse_exclusive {
+    ($context:expr, []) => {{
+        let _ = $context;
+        false
+    }};
+    ($context:expr, [$($condition:tt)+]) => {
+        ($($condition)+)($context)
+    };
 }
 
 #[macro_export]
@@ -149,27 +645,41 @@ macro_rules! _parse_ignore {
     ($context:expr $(,)?) => {};
 }
 
+/// Convert a value to a `RunResult`, preferring `Display` for errors, see `result::ErrorKind`
 #[macro_export]
 #[doc(hidden)]
-macro_rules! _test_expr {
-    ($context:expr, []) => {
-        run()
+macro_rules! _into_run_result {
+    ($result:expr) => {
+        $crate::_private::into_run_result!($result, _)
     };
-    ($context:expr, [$($params:tt)+]) => {
-        run($context)
+    ($result:expr, $ret:ty) => {
+        match $result {
+            result => {
+                #[allow(unused_imports)]
+                use $crate::_private::{ErrorKind as _, OtherKind as _};
+                (&result)
+                    .run_result_kind()
+                    .into_run_result(result, <$ret as $crate::IntoRunResult>::into_run_result)
+            }
+        }
     };
 }
 
+/// Run the test, converting the result to a `RunResult`
 #[macro_export]
 #[doc(hidden)]
 macro_rules! _run_test {
-    ($test:expr, [$expected:literal]) => {
+    ($test:expr, returns=$returns:tt, [$expected:literal]) => {
         $crate::panic::assert_panic_contains(|| $test, $expected)
+            .map_err($crate::RunError::with_cause)
     };
-    ($test:expr, []) => {
-        $crate::panic::assert_panic(|| $test)
+    ($test:expr, returns=$returns:tt, []) => {
+        $crate::panic::assert_panic(|| $test).map_err($crate::RunError::with_cause)
+    };
+    ($test:expr, returns=[] $(,)?) => {
+        <() as $crate::IntoRunResult>::into_run_result($test)
+    };
+    ($test:expr, returns=[$ret:ty] $(,)?) => {
+        $crate::_private::into_run_result!($test, $ret)
     };
-    ($test:expr $(,)?) => {{
-        $test
-    }};
 }
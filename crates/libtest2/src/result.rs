@@ -0,0 +1,55 @@
+//! Convert what a test returns into a [`RunResult`], see [`IntoRunResult`]
+
+use crate::IntoRunResult;
+use crate::RunError;
+use crate::RunResult;
+
+/// Picked for a `Result` whose error converts into a [`RunError`], reporting it with `Display`
+///
+/// Method resolution tries [`ErrorKind`] (`&self` is `&Result<T, E>`) before autoref'ing to
+/// [`OtherKind`] (`&self` is `&&R`), so this only works where the type is concrete, like in
+/// macros.
+pub trait ErrorKind {
+    fn run_result_kind(&self) -> ErrorTag {
+        ErrorTag
+    }
+}
+
+impl<T: IntoRunResult, E: Into<RunError>> ErrorKind for Result<T, E> {}
+
+pub struct ErrorTag;
+
+impl ErrorTag {
+    pub fn into_run_result<R: ErrorResult>(self, result: R, _: fn(R) -> RunResult) -> RunResult {
+        result.into_error_result()
+    }
+}
+
+pub trait ErrorResult {
+    fn into_error_result(self) -> RunResult;
+}
+
+impl<T: IntoRunResult, E: Into<RunError>> ErrorResult for Result<T, E> {
+    fn into_error_result(self) -> RunResult {
+        self.map_err(Into::into)?.into_run_result()
+    }
+}
+
+/// Picked for everything else, deferring to [`IntoRunResult`]
+pub trait OtherKind {
+    fn run_result_kind(&self) -> OtherTag {
+        OtherTag
+    }
+}
+
+impl<R> OtherKind for &R {}
+
+pub struct OtherTag;
+
+impl OtherTag {
+    /// `convert` is [`IntoRunResult::into_run_result`], named by the caller so an unsupported
+    /// type is reported there
+    pub fn into_run_result<R>(self, result: R, convert: fn(R) -> RunResult) -> RunResult {
+        convert(result)
+    }
+}
@@ -268,3 +268,201 @@ test result: ok. 2 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]
 "#]],
     );
 }
+
+#[test]
+fn help() {
+    test_cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+Usage: [..] [OPTIONS] [FILTER]...
+
+Arguments:
+      [FILTER]...     Skip tests whose name does not match one of the filters
+
+Options:
+      --fail-fast     Don't start new tests after the first failure
+      --skip FILTER   Skip tests whose names contain FILTER
+                      (this flag can be used multiple times)
+      --include-tag TAG
+                      Only run tests with TAG, or all of the tags in TAG+TAG
+                      (this flag can be used multiple times)
+      --exclude-tag TAG
+                      Skip tests with TAG, or all of the tags in TAG+TAG
+                      (this flag can be used multiple times)
+      --exact         Exactly match filters rather than by substring
+      --ignored       Run only ignored tests
+      --include-ignored
+                      Run ignored and not ignored tests
+      --test          Run tests and not benchmarks
+      --bench         Run benchmarks instead of tests
+      --no-capture    don't capture stdout/stderr of each task,
+                      allow printing directly
+      --shuffle       Run tests in random order (unstable)
+      --shuffle-seed SEED
+                      Run tests in random order, seeded with SEED (unstable)
+      --seed SEED     Seed randomized tests with SEED, like to reproduce a failure
+      --show-output   Show captured stdout of successful tests
+      --list          List all tests and benchmarks
+      --test-threads NUM
+                      Number of threads used for running tests in parallel
+                      (default is >1)
+      --format <pretty|terse|json>
+                      Configure formatting of output:
+                      - pretty: Print verbose output
+                      - terse: Display one character per test
+                      - json: Output a json document
+  -q, --quiet         Display one character per test instead of one line
+                      (alias to --format=terse)
+      --color <auto|always|never>
+                      Configure coloring of output:
+                      - auto: detect terminal support (default)
+                      - always: always colorize output
+                      - never: never colorize output
+  -Z FLAG             Enable nightly-only flags:
+                      - unstable-options: Allow use of experimental features
+      --no-config     Don't load defaults from `.config/libtest2.toml`
+
+"#]]);
+}
+
+#[test]
+fn similar_flag() {
+    test_cmd()
+        .arg("--include-ignord")
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: unexpected argument, found `--include-ignord`
+
+  [..] --include-ignord
+[..]^^^^^^^^^^^^^^^^
+
+tip: a similar argument exists: '--include-ignored'
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn abbreviated_flag() {
+    check(
+        &["--exa", "foo", "--test-thr=1"],
+        str![[r#"
+
+running 1 test
+test foo ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 1 test
+test foo ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn ambiguous_flag() {
+    test_cmd()
+        .arg("--sh")
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: ambiguous argument, could be `--shuffle`, `--shuffle-seed` or `--show-output`, found `--sh`
+
+  [..] --sh
+[..]^^^^
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn abbreviated_flag_missing_value() {
+    test_cmd()
+        .args(["--exact", "--test-thr"])
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: missing required `NUM` when parsing `--test-threads`
+
+  [..] --exact --test-thr
+[..]^^^^^^^^^^
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn similar_value() {
+    test_cmd()
+        .args(["--color", "alway"])
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: expected one of `auto`, `always`, `never`, found `alway` when parsing `--color`
+
+  [..] --color alway
+[..]^^^^^
+
+tip: a similar value exists: 'always'
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn error_in_long_command_line() {
+    test_cmd()
+        .args([
+            "a",
+            "b",
+            "c",
+            "d",
+            "--test-threads",
+            "zero",
+            "e",
+            "f",
+            "g",
+            "h",
+        ])
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: invalid digit found in string, found `zero` when parsing `--test-threads`
+
+  [..] ... c d --test-threads zero e f g ...
+[..]^^^^
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
@@ -188,3 +188,176 @@ fn invalid() {
     let argfile = std::path::Path::new("highly-improbably-non-existent-file.txt");
     check(&[], argfile, 1, str![""], str![""]);
 }
+
+#[test]
+fn quoting() {
+    let argfile = crate::util::new_file(
+        "argfile-",
+        ".txt",
+        r#"--exact 'one' "two"
+three
+"#,
+    );
+    check(
+        &[],
+        &argfile,
+        0,
+        str![[r#"
+
+running 3 tests
+test one   ... ok
+test two   ... ok
+test three ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 3 tests
+...
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn comments() {
+    let argfile = crate::util::new_file(
+        "argfile-",
+        ".txt",
+        "# Shard 1 of 2
+
+'one'  # trailing comment
+'#two'
+",
+    );
+    check(
+        &["--exact"],
+        &argfile,
+        0,
+        str![[r#"
+
+running 1 test
+test one ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 1 test
+test one ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn nested() {
+    let inner = crate::util::new_file("argfile-", ".txt", "two\n--exact");
+    let inner_name = inner.file_name().unwrap().to_str().unwrap();
+    let argfile = crate::util::new_file("argfile-", ".txt", &format!("one\n@{inner_name}"));
+    check(
+        &[],
+        &argfile,
+        0,
+        str![[r#"
+
+running 2 tests
+test one ... ok
+test two ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 2 tests
+...
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn cycle() {
+    let argfile = crate::util::new_file("argfile-", ".txt", "");
+    let name = argfile.file_name().unwrap().to_str().unwrap();
+    std::fs::write(&argfile, format!("one\n@{name}")).unwrap();
+    test_cmd()
+        .arg(format!("@{}", argfile.to_str().unwrap()))
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+[..]argfile-[..].txt:2: `@[..]argfile-[..].txt` includes itself
+
+"#]]);
+}
+
+#[test]
+fn unterminated_quote() {
+    let argfile = crate::util::new_file("argfile-", ".txt", "one\n'two\nthree");
+    test_cmd()
+        .arg(format!("@{}", argfile.to_str().unwrap()))
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+[..]argfile-[..].txt:2: unterminated `'`
+
+"#]]);
+}
+
+#[test]
+fn verbatim_lines() {
+    let argfile =
+        crate::util::new_file("argfile-", ".txt", "--exact\n  one\n--color=C:\\dir\\file");
+    test_cmd()
+        .arg(format!("@{}", argfile.to_str().unwrap()))
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: expected one of `auto`, `always`, `never`, found `C:/dir/file` when parsing `--color`
+
+  [..] --exact '  one' --color=C:/dir/file
+                                              ^^^^^^^^^^^
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn missing_nested() {
+    let argfile = crate::util::new_file(
+        "argfile-",
+        ".txt",
+        "one\n@highly-improbably-non-existent-file.txt",
+    );
+    test_cmd()
+        .arg(format!("@{}", argfile.to_str().unwrap()))
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+[..]argfile-[..].txt:2: [..]highly-improbably-non-existent-file.txt: [..]
+
+"#]]);
+}
@@ -0,0 +1,135 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use libtest2::RunError;
+use libtest2::RunResult;
+use libtest2::TestContext;
+
+#[libtest2::main(executor = counted)]
+fn main() {}
+
+static COUNTED: AtomicUsize = AtomicUsize::new(0);
+
+fn counted<F: std::future::Future>(future: F) -> F::Output {
+    COUNTED.fetch_add(1, Ordering::SeqCst);
+    libtest2::executor::block_on(future)
+}
+
+fn detached<F: std::future::Future>(future: F) -> F::Output {
+    libtest2::executor::block_on(future)
+}
+
+/// Completes on another thread, to exercise waking
+async fn answer() -> u32 {
+    let state = std::sync::Arc::new(std::sync::Mutex::new((None, None::<std::task::Waker>)));
+    let mut spawned = false;
+    std::future::poll_fn(move |cx| {
+        let mut guard = state.lock().unwrap();
+        if let Some(value) = guard.0 {
+            return std::task::Poll::Ready(value);
+        }
+        guard.1 = Some(cx.waker().clone());
+        if !spawned {
+            spawned = true;
+            let state = state.clone();
+            std::thread::spawn(move || {
+                let mut guard = state.lock().unwrap();
+                guard.0 = Some(42);
+                guard.1.take().unwrap().wake();
+            });
+        }
+        std::task::Poll::Pending
+    })
+    .await
+}
+
+#[libtest2::test]
+async fn awaits(_context: &TestContext) {
+    assert_eq!(answer().await, 42);
+    assert_ne!(COUNTED.load(Ordering::SeqCst), 0);
+}
+
+#[libtest2::test]
+async fn no_context() {}
+
+#[libtest2::test]
+async fn fails(_context: &TestContext) -> RunResult {
+    let value = answer().await;
+    Err(RunError::fail(format!("got {value}")))
+}
+
+#[libtest2::test]
+#[should_panic = "expected panic"]
+async fn panics(_context: &TestContext) {
+    answer().await;
+    panic!("expected panic");
+}
+
+#[libtest2::test]
+#[executor(detached)]
+async fn custom_executor(_context: &TestContext) -> Result<(), std::io::Error> {
+    let before = COUNTED.load(Ordering::SeqCst);
+    std::future::ready(()).await;
+    assert_eq!(COUNTED.load(Ordering::SeqCst), before);
+    Ok(())
+}
+
+struct Name(&'static str);
+
+impl libtest2::Fixture for Name {
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        Ok(Name("fixture"))
+    }
+}
+
+#[libtest2::test]
+async fn with_fixture(_: &TestContext, name: &Name) {
+    assert_eq!(std::future::ready(name.0).await, "fixture");
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 6 tests
+test awaits          ... ok
+test custom_executor ... ok
+test fails           ... FAILED
+test no_context      ... ok
+test panics          ... ok
+test with_fixture    ... ok
+
+failures:
+
+---- fails ----
+got 42
+
+
+failures:
+    fails
+
+test result: FAILED. 5 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,155 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use libtest2::Bencher;
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn check(_context: &TestContext) {}
+
+#[libtest2::bench]
+fn sum(b: &mut Bencher) {
+    let calls = AtomicUsize::new(0);
+    b.iter(|| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        (0..100).sum::<u64>()
+    });
+    println!("called {}", if calls.into_inner() == 1 { "once" } else { "many times" });
+}
+
+#[libtest2::bench]
+#[ignore = "too slow"]
+fn slow(b: &mut Bencher) {
+    b.iter(|| std::thread::sleep(std::time::Duration::from_secs(1)));
+}
+
+#[libtest2::bench]
+fn fails(_b: &mut Bencher) -> Result<(), String> {
+    Err("no baseline".to_owned())
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn test_mode() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 4 tests
+test check ... ok
+test fails ... FAILED
+test slow  ... ignored
+test sum   ... called once
+ok
+
+failures:
+
+---- fails ----
+"no baseline"
+
+
+failures:
+    fails
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn bench_mode() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=json",
+            "--bench",
+            "--exact",
+            "sum",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"check","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"fails","mode":"bench","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"slow","mode":"bench","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"sum","mode":"bench","elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+{"event":"run_start","seed":[..],"elapsed_s":"[..]"}
+{"event":"case_start","name":"sum","elapsed_s":"[..]"}
+called many times
+{"event":"extension","extension":"libtest2.bench","name":"sum","payload":{"median_ns":[..],"deviation_ns":[..]},"elapsed_s":"[..]"}
+{"event":"case_complete","name":"sum","elapsed_s":"[..]"}
+{"event":"run_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
+
+#[test]
+fn bench_mode_pretty() {
+    test_cmd()
+        .args(["--bench", "--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 4 tests
+test check ... ignored
+test fails ... FAILED
+test slow  ... ignored
+test sum   ... called many times
+bench: [..] ns/iter (+/- [..])
+
+failures:
+
+---- fails ----
+"no baseline"
+
+
+failures:
+    fails
+
+test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn list() {
+    test_cmd()
+        .args(["--list"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+check: test
+fails: bench
+slow: bench
+sum: bench
+
+4 tests
+
+
+"#]]);
+}
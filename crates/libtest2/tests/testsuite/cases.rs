@@ -0,0 +1,179 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+#[case(1, 2)]
+#[case::negative(-1, 0)]
+#[case(2, 4)]
+fn add_one(_context: &libtest2::TestContext, input: i32, expected: i32) -> libtest2::RunResult {
+    if input + 1 == expected {
+        Ok(())
+    } else {
+        Err(libtest2::RunError::fail(format!("{input} + 1 != {expected}")))
+    }
+}
+
+#[libtest2::test]
+#[ignore = "slow"]
+#[case("a")]
+#[case("b")]
+fn ignored(_context: &libtest2::TestContext, _input: &str) {}
+
+#[libtest2::test]
+#[case::empty("")]
+#[case::blank(" ")]
+#[should_panic = "empty"]
+fn panics(_context: &libtest2::TestContext, input: &str) {
+    assert!(!input.trim().is_empty(), "input is empty");
+}
+
+#[libtest2::test]
+#[case(std::collections::HashMap::<u8, u8>::new(), 0)]
+fn turbofish(
+    _context: &libtest2::TestContext,
+    input: std::collections::HashMap<u8, u8>,
+    expected: usize,
+) {
+    assert_eq!(input.len(), expected);
+}
+
+#[libtest2::test]
+#[case::add(|a: u8, b: u8| a + b, 3)]
+#[case::less(move |a, b| u8::from(a < b), 1)]
+fn closure(_context: &libtest2::TestContext, op: fn(u8, u8) -> u8, expected: u8) {
+    assert_eq!(op(1, 2), expected);
+}
+
+#[libtest2::test]
+#[case(1 < 2, true)]
+fn less_than(_context: &libtest2::TestContext, input: bool, expected: bool) {
+    assert_eq!(input, expected);
+}
+
+mod nested {
+    #[libtest2::test]
+    #[case(vec![1, 2])]
+    fn sum(_context: &libtest2::TestContext, input: Vec<u8>) {
+        assert_eq!(input.iter().sum::<u8>(), 3);
+    }
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 12 tests
+test add_one::case_1     ... ok
+test add_one::case_3     ... FAILED
+test add_one::negative   ... ok
+test closure::add        ... ok
+test closure::less       ... ok
+test ignored::case_1     ... ignored
+test ignored::case_2     ... ignored
+test less_than::case_1   ... ok
+test nested::sum::case_1 ... ok
+test panics::blank       ... ok
+test panics::empty       ... ok
+test turbofish::case_1   ... ok
+
+failures:
+
+---- add_one::case_3 ----
+2 + 1 != 4
+
+
+failures:
+    add_one::case_3
+
+test result: FAILED. 9 passed; 1 failed; 2 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn list() {
+    test_cmd()
+        .arg("--list")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+add_one::case_1: test
+add_one::case_3: test
+add_one::negative: test
+closure::add: test
+closure::less: test
+ignored::case_1: test
+ignored::case_2: test
+less_than::case_1: test
+nested::sum::case_1: test
+panics::blank: test
+panics::empty: test
+turbofish::case_1: test
+
+12 tests
+
+
+"#]]);
+}
+
+#[test]
+fn filter_one_case() {
+    test_cmd()
+        .args(["--exact", "add_one::case_1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test add_one::case_1 ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 11 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn skip_one_case() {
+    test_cmd()
+        .args([
+            "add_one",
+            "--skip",
+            "add_one::case_3",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test add_one::case_1   ... ok
+test add_one::negative ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 10 filtered out; finished in [..]s
+
+
+"#]]);
+}
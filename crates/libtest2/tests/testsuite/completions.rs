@@ -0,0 +1,199 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn foo(_context: &libtest2::TestContext) {
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn bash() {
+    test_cmd()
+        .args(["--generate-completions", "bash"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+_[..]_tests() {
+    "${COMP_WORDS[0]}" --list 2>/dev/null | sed -n 's/: [a-z]*$//p'
+}
+
+_[..]() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    # `COMP_WORDBREAKS` splits `--flag=value` into `--flag`, `=`, and `value`
+    if [[ "$cur" == "=" ]]; then
+        cur=""
+    elif [[ "$prev" == "=" ]]; then
+        prev="${COMP_WORDS[COMP_CWORD-2]}"
+    fi
+    case "$prev" in
+        --skip)
+            COMPREPLY=($(compgen -W "$(_[..]_tests)" -- "$cur"))
+            return 0
+            ;;
+        --include-tag)
+            COMPREPLY=()
+            return 0
+            ;;
+        --exclude-tag)
+            COMPREPLY=()
+            return 0
+            ;;
+        --shuffle-seed)
+            COMPREPLY=()
+            return 0
+            ;;
+        --seed)
+            COMPREPLY=()
+            return 0
+            ;;
+        --test-threads)
+            COMPREPLY=()
+            return 0
+            ;;
+        --format)
+            COMPREPLY=($(compgen -W "pretty terse json" -- "$cur"))
+            return 0
+            ;;
+        --color)
+            COMPREPLY=($(compgen -W "auto always never" -- "$cur"))
+            return 0
+            ;;
+        -Z)
+            COMPREPLY=()
+            return 0
+            ;;
+    esac
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "--fail-fast --skip --include-tag --exclude-tag --exact --ignored --include-ignored --test --bench --no-capture --shuffle --shuffle-seed --seed --show-output --list --test-threads --format -q --quiet --color -Z --no-config" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -W "$(_[..]_tests)" -- "$cur"))
+    fi
+    return 0
+}
+
+complete -F _[..] [..]
+
+"#]]);
+}
+
+#[test]
+fn zsh() {
+    test_cmd()
+        .args(["--generate-completions", "zsh"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r##"
+#compdef [..]
+
+_[..]_tests() {
+    local -a tests
+    tests=(${(f)"$("${words[1]}" --list 2>/dev/null | sed -n 's/: [a-z]*$//p')"})
+    compadd -a tests
+}
+
+_[..]() {
+    _arguments -s /
+        '*:FILTER:_[..]_tests' /
+        '--fail-fast[Don'/''t start new tests after the first failure]' /
+        '*--skip=[Skip tests whose names contain FILTER]:FILTER:_[..]_tests' /
+        '*--include-tag=[Only run tests with TAG, or all of the tags in TAG+TAG]:TAG:' /
+        '*--exclude-tag=[Skip tests with TAG, or all of the tags in TAG+TAG]:TAG:' /
+        '--exact[Exactly match filters rather than by substring]' /
+        '--ignored[Run only ignored tests]' /
+        '--include-ignored[Run ignored and not ignored tests]' /
+        '--test[Run tests and not benchmarks]' /
+        '--bench[Run benchmarks instead of tests]' /
+        '--no-capture[don'/''t capture stdout/stderr of each task]' /
+        '--shuffle[Run tests in random order (unstable)]' /
+        '--shuffle-seed=[Run tests in random order, seeded with SEED (unstable)]:SEED:' /
+        '--seed=[Seed randomized tests with SEED, like to reproduce a failure]:SEED:' /
+        '--show-output[Show captured stdout of successful tests]' /
+        '--list[List all tests and benchmarks]' /
+        '--test-threads=[Number of threads used for running tests in parallel]:NUM:' /
+        '--format=[Configure formatting of output]:FORMAT:(pretty terse json)' /
+        '(-q --quiet)-q[Display one character per test instead of one line]' /
+        '(-q --quiet)--quiet[Display one character per test instead of one line]' /
+        '--color=[Configure coloring of output]:WHEN:(auto always never)' /
+        '-Z+[Enable nightly-only flags]:FLAG:' /
+        '--no-config[Don'/''t load defaults from `.config/libtest2.toml`]' /
+        && return 0
+}
+
+compdef _[..] [..]
+
+"##]]);
+}
+
+#[test]
+fn fish() {
+    test_cmd()
+        .args(["--generate-completions", "fish"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+function __[..]_tests
+    set -l cmd (commandline -opc)
+    $cmd[1] --list 2>/dev/null | string replace -rf ': [a-z]+$' ''
+end
+
+complete -c [..] -f -a '(__[..]_tests)'
+complete -c [..] -l fail-fast -d 'Don/'t start new tests after the first failure'
+complete -c [..] -l skip -x -a '(__[..]_tests)' -d 'Skip tests whose names contain FILTER'
+complete -c [..] -l include-tag -x -d 'Only run tests with TAG, or all of the tags in TAG+TAG'
+complete -c [..] -l exclude-tag -x -d 'Skip tests with TAG, or all of the tags in TAG+TAG'
+complete -c [..] -l exact -d 'Exactly match filters rather than by substring'
+complete -c [..] -l ignored -d 'Run only ignored tests'
+complete -c [..] -l include-ignored -d 'Run ignored and not ignored tests'
+complete -c [..] -l test -d 'Run tests and not benchmarks'
+complete -c [..] -l bench -d 'Run benchmarks instead of tests'
+complete -c [..] -l no-capture -d 'don/'t capture stdout/stderr of each task'
+complete -c [..] -l shuffle -d 'Run tests in random order (unstable)'
+complete -c [..] -l shuffle-seed -x -d 'Run tests in random order, seeded with SEED (unstable)'
+complete -c [..] -l seed -x -d 'Seed randomized tests with SEED, like to reproduce a failure'
+complete -c [..] -l show-output -d 'Show captured stdout of successful tests'
+complete -c [..] -l list -d 'List all tests and benchmarks'
+complete -c [..] -l test-threads -x -d 'Number of threads used for running tests in parallel'
+complete -c [..] -l format -x -a 'pretty terse json' -d 'Configure formatting of output'
+complete -c [..] -s q -l quiet -d 'Display one character per test instead of one line'
+complete -c [..] -l color -x -a 'auto always never' -d 'Configure coloring of output'
+complete -c [..] -s Z -x -d 'Enable nightly-only flags'
+complete -c [..] -l no-config -d 'Don/'t load defaults from `.config/libtest2.toml`'
+
+"#]]);
+}
+
+#[test]
+fn unknown_shell() {
+    test_cmd()
+        .args(["--generate-completions", "powershell"])
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: expected one of `bash`, `zsh`, `fish`, found `powershell` when parsing `--generate-completions`
+
+  [..] --generate-completions powershell
+[..]^^^^^^^^^^
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
@@ -0,0 +1,220 @@
+use snapbox::str;
+
+fn test_cmd(config: &str) -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn foo(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn bar(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn barro(_context: &libtest2::TestContext) {
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+
+    // Run from a subdirectory to check the config is discovered upward
+    let project = crate::util::new_dir("config");
+    std::fs::create_dir_all(project.join(".config")).unwrap();
+    std::fs::write(project.join(".config/libtest2.toml"), config).unwrap();
+    let current_dir = project.join("src");
+    std::fs::create_dir_all(&current_dir).unwrap();
+
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_TEST_THREADS")
+}
+
+#[test]
+fn defaults() {
+    test_cmd(
+        r#"
+# Team defaults
+test-threads = 1
+skip = [
+    "barro",
+]
+"#,
+    )
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+
+running 2 tests
+test bar ... ok
+test foo ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn cli_overrides() {
+    test_cmd(
+        r#"
+test-threads = 2
+skip = ["barro"]
+"#,
+    )
+    .args(["--test-threads", "1", "--skip", "foo"])
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+
+running 2 tests
+test bar   ... ok
+test barro ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 1 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn cli_overrides_related() {
+    test_cmd(
+        r#"
+test-threads = 1
+include-ignored = true
+"#,
+    )
+    .args(["--ignored"])
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+
+running 3 tests
+test bar   ... ok
+test barro ... ok
+test foo   ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn env_overrides() {
+    test_cmd(
+        r#"
+test-threads = 0
+"#,
+    )
+    .env("RUST_TEST_THREADS", "1")
+    .args(["--exact", "foo"])
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+
+running 1 test
+test foo ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn no_config() {
+    test_cmd(
+        r#"
+unknown = true
+"#,
+    )
+    .args(["--no-config", "--test-threads", "1"])
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+
+running 3 tests
+test bar   ... ok
+test barro ... ok
+test foo   ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn unknown_key() {
+    test_cmd(
+        r#"
+exact = true
+test-thread = 1
+"#,
+    )
+    .assert()
+    .code(1)
+    .stdout_eq("")
+    .stderr_eq(str![[r#"
+error: [..]/.config/libtest2.toml:3: unknown key `test-thread`
+
+tip: a similar argument exists: 'test-threads'
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn invalid_value() {
+    test_cmd(
+        r#"
+test-threads = "many"
+"#,
+    )
+    .assert()
+    .code(1)
+    .stdout_eq("")
+    .stderr_eq(str![[r#"
+error: [..]/.config/libtest2.toml:2: invalid digit found in string, found `many` when parsing `--test-threads`
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn invalid_syntax() {
+    test_cmd(
+        r#"
+[libtest2]
+exact = true
+"#,
+    )
+    .assert()
+    .code(1)
+    .stdout_eq("")
+    .stderr_eq(str![[r#"
+error: [..]/.config/libtest2.toml:2: tables are not supported
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
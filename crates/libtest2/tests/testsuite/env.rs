@@ -0,0 +1,270 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn a(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn b(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn c(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn d(_context: &libtest2::TestContext) {
+}
+
+#[libtest2::test]
+fn e(_context: &libtest2::TestContext) {
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_TEST_THREADS")
+        .env_remove("RUST_TEST_NOCAPTURE")
+        .env_remove("RUST_TEST_SHUFFLE")
+        .env_remove("RUST_TEST_SHUFFLE_SEED")
+}
+
+#[test]
+fn test_threads() {
+    test_cmd()
+        .env("RUST_TEST_THREADS", "1")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test a ... ok
+test b ... ok
+test c ... ok
+test d ... ok
+test e ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn test_threads_invalid() {
+    test_cmd()
+        .env("RUST_TEST_THREADS", "0")
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: `RUST_TEST_THREADS` is `0`, should be a positive integer
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn test_threads_overridden_by_cli() {
+    test_cmd()
+        .env("RUST_TEST_THREADS", "invalid")
+        .args(["--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test a ... ok
+test b ... ok
+test c ... ok
+test d ... ok
+test e ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn nocapture() {
+    test_cmd()
+        .env("RUST_TEST_NOCAPTURE", "1")
+        .args(["--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test a ... ok
+test b ... ok
+test c ... ok
+test d ... ok
+test e ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn nocapture_legacy_flag() {
+    test_cmd()
+        .args(["--nocapture", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test a ... ok
+test b ... ok
+test c ... ok
+test d ... ok
+test e ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn shuffle_seed() {
+    let expected = str![[r#"
+
+running 5 tests, shuffle seed: 2
+test a ... ok
+test d ... ok
+test b ... ok
+test e ... ok
+test c ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]];
+    test_cmd()
+        .env("RUST_TEST_SHUFFLE_SEED", "2")
+        .args(["-Zunstable-options", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(expected.clone());
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--shuffle-seed",
+            "2",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(expected.clone());
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--shuffle",
+            "--seed",
+            "2",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(expected);
+}
+
+#[test]
+fn shuffle_seed_overridden_by_cli() {
+    test_cmd()
+        .env("RUST_TEST_SHUFFLE_SEED", "invalid")
+        .args([
+            "-Zunstable-options",
+            "--shuffle-seed",
+            "2",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn shuffle_seed_invalid() {
+    test_cmd()
+        .env("RUST_TEST_SHUFFLE_SEED", "invalid")
+        .args(["-Zunstable-options"])
+        .assert()
+        .code(1)
+        .stdout_eq("")
+        .stderr_eq(str![[r#"
+error: `RUST_TEST_SHUFFLE_SEED` is `invalid`, should be a number
+
+Usage: [..] [OPTIONS] [FILTER]...
+
+For more information, try '--help'.
+
+"#]]);
+}
+
+#[test]
+fn shuffle() {
+    test_cmd()
+        .env("RUST_TEST_SHUFFLE", "1")
+        .args(["-Zunstable-options", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests, shuffle seed: [..]
+test [..] ... ok
+test [..] ... ok
+test [..] ... ok
+test [..] ... ok
+test [..] ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn shuffle_requires_unstable() {
+    test_cmd()
+        .env("RUST_TEST_SHUFFLE", "1")
+        .env("RUST_TEST_SHUFFLE_SEED", "2")
+        .args(["--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test a ... ok
+test b ... ok
+test c ... ok
+test d ... ok
+test e ... ok
+
+test result: ok. 5 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,94 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+fn concurrently() {
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    RUNNING.fetch_sub(1, Ordering::SeqCst);
+}
+
+fn alone() {
+    assert_eq!(RUNNING.load(Ordering::SeqCst), 0);
+    std::env::set_var("LIBTEST2_EXCLUSIVE", "1");
+    std::env::remove_var("LIBTEST2_EXCLUSIVE");
+}
+
+#[libtest2::test]
+fn concurrent_one(_context: &TestContext) {
+    concurrently();
+}
+
+#[libtest2::test]
+fn concurrent_two(_context: &TestContext) {
+    concurrently();
+}
+
+#[libtest2::test]
+#[exclusive(|_| false)]
+fn concurrent_unless(_context: &TestContext) {
+    concurrently();
+}
+
+#[libtest2::test]
+#[exclusive]
+fn exclusive(_context: &TestContext) {
+    alone();
+}
+
+#[libtest2::test]
+#[exclusive(|context: &TestContext| context.test_name().is_empty())]
+fn exclusive_if(_context: &TestContext) {
+    alone();
+}
+
+#[libtest2::test]
+#[serial]
+#[case(1)]
+#[case(2)]
+fn serial(_context: &TestContext, _row: u32) {
+    alone();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "4"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 7 tests
+...
+test exclusive         ... ok
+test exclusive_if      ... ok
+test serial::case_1    ... ok
+test serial::case_2    ... ok
+
+test result: ok. 7 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,137 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r##"
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn coverage(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.notify_extension("my_harness.coverage", r#"{"lines": 42, "covered": [1, 2]}"#)?;
+    Ok(())
+}
+
+#[libtest2::test]
+fn invalid_payload(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.notify_extension("my_harness.coverage", r#"{"lines": "#)?;
+    Ok(())
+}
+"##,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd().assert().code(101).stdout_eq(str![[r#"
+
+running 2 tests
+test coverage        ... ok
+test invalid_payload ... FAILED
+
+failures:
+
+---- invalid_payload ----
+`my_harness.coverage` payload is not valid JSON
+
+
+failures:
+    invalid_payload
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn json() {
+    test_cmd()
+        .args(["-Zunstable-options", "--format=json"])
+        .assert()
+        .code(101)
+        .stdout_eq(
+            str![[r#"
+[
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_start"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "coverage"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_case",
+    "name": "invalid_payload"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "discover_complete"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_start",
+    "seed": "{...}"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "coverage"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "extension",
+    "extension": "my_harness.coverage",
+    "name": "coverage",
+    "payload": {
+      "covered": [
+        1,
+        2
+      ],
+      "lines": 42
+    }
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "coverage"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_start",
+    "name": "invalid_payload"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_message",
+    "kind": "error",
+    "message": "`my_harness.coverage` payload is not valid JSON",
+    "name": "invalid_payload"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "case_complete",
+    "name": "invalid_payload"
+  },
+  {
+    "elapsed_s": "[..]",
+    "event": "run_complete"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        );
+}
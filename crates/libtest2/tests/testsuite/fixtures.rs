@@ -0,0 +1,196 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use libtest2::Fixture;
+use libtest2::RunError;
+use libtest2::RunResult;
+use libtest2::Scope;
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+static SETUPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Database(usize);
+
+impl Fixture for Database {
+    const SCOPE: Scope = Scope::Run;
+
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        println!("setup database");
+        Ok(Database(SETUPS.fetch_add(1, Ordering::SeqCst)))
+    }
+
+    fn teardown(self) -> RunResult {
+        println!("teardown database");
+        Ok(())
+    }
+}
+
+struct Scratch(String);
+
+impl Fixture for Scratch {
+    fn setup(context: &TestContext) -> Result<Self, RunError> {
+        println!("setup scratch for {}", context.test_name());
+        Ok(Scratch(context.test_name().to_owned()))
+    }
+
+    fn teardown(self) -> RunResult {
+        println!("teardown scratch for {}", self.0);
+        Ok(())
+    }
+}
+
+struct Offline;
+
+impl Fixture for Offline {
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        Err(RunError::fail("no network"))
+    }
+}
+
+struct Leaky;
+
+impl Fixture for Leaky {
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        Ok(Leaky)
+    }
+
+    fn teardown(self) -> RunResult {
+        Err(RunError::fail("still in use"))
+    }
+}
+
+struct Pool;
+
+impl Fixture for Pool {
+    const SCOPE: Scope = Scope::Run;
+
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        Ok(Pool)
+    }
+
+    fn teardown(self) -> RunResult {
+        Err(RunError::fail("connections still open"))
+    }
+}
+
+#[libtest2::test]
+fn one(_context: &TestContext, db: &Database, scratch: &Scratch) {
+    assert_eq!(db.0, 0);
+    assert_eq!(scratch.0, "one");
+}
+
+#[libtest2::test]
+fn two(_context: &TestContext, db: &Database, _pool: &Pool) {
+    assert_eq!(db.0, 0);
+}
+
+#[libtest2::test]
+fn panics(_context: &TestContext, _scratch: &Scratch) {
+    panic!("oops");
+}
+
+#[libtest2::test]
+fn setup_fails(_context: &TestContext, _scratch: &Scratch, _offline: &Offline) {
+    unreachable!("setup failed");
+}
+
+#[libtest2::test]
+fn teardown_fails(_context: &TestContext, _leaky: &Leaky) {}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 5 tests
+test one            ... setup database
+setup scratch for one
+teardown scratch for one
+ok
+test panics         ... setup scratch for panics
+teardown scratch for panics
+FAILED
+test setup_fails    ... setup scratch for setup_fails
+teardown scratch for setup_fails
+FAILED
+test teardown_fails ... FAILED
+test two            ... ok
+teardown database
+
+failures:
+
+---- panics ----
+test panicked: oops
+
+---- setup_fails ----
+fixture `[..]::Offline` failed to set up: no network
+
+---- teardown_fails ----
+fixture `[..]::Leaky` failed to tear down: still in use
+
+---- run ----
+fixture `[..]::Pool` failed to tear down: connections still open
+
+
+failures:
+    panics
+    setup_fails
+    teardown_fails
+
+test result: FAILED. 2 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn json() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=json",
+            "--exact",
+            "setup_fails",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"one","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"panics","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"setup_fails","elapsed_s":"[..]"}
+{"event":"discover_case","name":"teardown_fails","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_case","name":"two","selected":false,"elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+{"event":"run_start","seed":[..],"elapsed_s":"[..]"}
+{"event":"case_start","name":"setup_fails","elapsed_s":"[..]"}
+setup scratch for setup_fails
+teardown scratch for setup_fails
+{"event":"case_message","name":"setup_fails","kind":"fixture","message":"fixture `[..]::Offline` failed to set up: no network","elapsed_s":"[..]"}
+{"event":"case_complete","name":"setup_fails","elapsed_s":"[..]"}
+{"event":"run_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
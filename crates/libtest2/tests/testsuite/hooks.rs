@@ -0,0 +1,222 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::RunResult;
+use libtest2::TestContext;
+
+fn hook(name: &str) -> RunResult {
+    println!("{name}");
+    if std::env::var("LIBTEST2_FAIL_HOOK").as_deref() == Ok(name) {
+        return Err(RunError::fail(format!("{name} is broken")));
+    }
+    Ok(())
+}
+
+fn before_all() -> RunResult {
+    hook("before_all")
+}
+
+fn after_all() -> RunResult {
+    hook("after_all")
+}
+
+#[libtest2::main(
+    before_all = before_all,
+    after_all = after_all,
+    before_each = |context: &TestContext| hook(&format!("before_each {}", context.test_name())),
+    after_each = |context: &TestContext| hook(&format!("after_each {}", context.test_name())),
+)]
+fn main() {}
+
+#[libtest2::test]
+fn one(_context: &TestContext) {
+    println!("one");
+}
+
+#[libtest2::test]
+fn two(_context: &TestContext) {
+    println!("two");
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 2 tests
+before_all
+test one ... before_each one
+one
+after_each one
+ok
+test two ... before_each two
+two
+after_each two
+ok
+after_all
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn list() {
+    test_cmd()
+        .args(["--list"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+one: test
+two: test
+
+2 tests
+
+
+"#]]);
+}
+
+#[test]
+fn before_all_fails() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .env("LIBTEST2_FAIL_HOOK", "before_all")
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+before_all
+after_all
+
+failures:
+
+---- run ----
+`before_all` failed: before_all is broken
+
+
+test result: FAILED. 0 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn after_all_fails() {
+    test_cmd()
+        .args(["--test-threads", "1", "--format", "json", "-Zunstable-options"])
+        .env("LIBTEST2_FAIL_HOOK", "after_all")
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"one","elapsed_s":"[..]"}
+{"event":"discover_case","name":"two","elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+{"event":"run_start","seed":[..],"elapsed_s":"[..]"}
+before_all
+{"event":"case_start","name":"one","elapsed_s":"[..]"}
+before_each one
+one
+after_each one
+{"event":"case_complete","name":"one","elapsed_s":"[..]"}
+{"event":"case_start","name":"two","elapsed_s":"[..]"}
+before_each two
+two
+after_each two
+{"event":"case_complete","name":"two","elapsed_s":"[..]"}
+after_all
+{"event":"run_message","kind":"fixture","message":"`after_all` failed: after_all is broken","elapsed_s":"[..]"}
+{"event":"run_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
+
+#[test]
+fn before_each_fails() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .env("LIBTEST2_FAIL_HOOK", "before_each one")
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+before_all
+test one ... before_each one
+after_each one
+FAILED
+test two ... before_each two
+two
+after_each two
+ok
+after_all
+
+failures:
+
+---- one ----
+`before_each` failed: before_each one is broken
+
+
+failures:
+    one
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn after_each_fails() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .env("LIBTEST2_FAIL_HOOK", "after_each two")
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+before_all
+test one ... before_each one
+one
+after_each one
+ok
+test two ... before_each two
+two
+after_each two
+FAILED
+after_all
+
+failures:
+
+---- two ----
+`after_each` failed: after_each two is broken
+
+
+failures:
+    two
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
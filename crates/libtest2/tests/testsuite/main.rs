@@ -1,9 +1,23 @@
 mod all_passing;
 mod argfile;
+mod async_fn;
+mod bench;
+mod cases;
+mod completions;
+mod config;
+mod env;
+mod exclusive;
+mod extension;
+mod fixtures;
+mod hooks;
 mod macros;
 mod mixed_bag;
 mod panic;
+mod return_types;
+mod seed;
 mod should_panic;
+mod source;
+mod tags;
 mod util;
 
 pub use util::*;
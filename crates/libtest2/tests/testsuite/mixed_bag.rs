@@ -254,38 +254,26 @@ test result: FAILED. 3 passed; 2 failed; 6 ignored; 0 filtered out; finished in
 
 #[test]
 fn bench_mode() {
+    // Only benchmarks are run
     check(
         &["--bench"],
-        101,
+        0,
         str![[r#"
 
 running 11 tests
-test cfg_in                  ... ok
-test custom_error            ... FAILED
-test failed                  ... FAILED
+test cfg_in                  ... ignored
+test custom_error            ... ignored
+test failed                  ... ignored
 test ignore_attribute        ... ignored
 test ignore_attribute_reason ... ignored
 test ignore_failed           ... ignored
 test ignore_passed           ... ignored
 test later_ignore_failed     ... ignored
 test later_ignore_passed     ... ignored
-test later_passed            ... ok
-test passed                  ... ok
-
-failures:
+test later_passed            ... ignored
+test passed                  ... ignored
 
----- custom_error ----
-I failed
-
----- failed ----
-was not a good boy
-
-
-failures:
-    custom_error
-    failed
-
-test result: FAILED. 3 passed; 2 failed; 6 ignored; 0 filtered out; finished in [..]s
+test result: ok. 0 passed; 0 failed; 11 ignored; 0 filtered out; finished in [..]s
 
 
 "#]],
@@ -294,20 +282,7 @@ test result: FAILED. 3 passed; 2 failed; 6 ignored; 0 filtered out; finished in
 running 11 tests
 ...
 
-failures:
-
----- custom_error ----
-I failed
-
----- failed ----
-was not a good boy
-
-
-failures:
-    custom_error
-    failed
-
-test result: FAILED. 3 passed; 2 failed; 6 ignored; 0 filtered out; finished in [..]s
+test result: ok. 0 passed; 0 failed; 11 ignored; 0 filtered out; finished in [..]s
 
 
 "#]],
@@ -1145,7 +1120,8 @@ fn json_filter() {
   },
   {
     "elapsed_s": "[..]",
-    "event": "run_start"
+    "event": "run_start",
+    "seed": "{...}"
   },
   {
     "elapsed_s": "[..]",
@@ -1305,7 +1281,8 @@ fn json_filter() {
   },
   {
     "elapsed_s": "[..]",
-    "event": "run_start"
+    "event": "run_start",
+    "seed": "{...}"
   },
   {
     "elapsed_s": "[..]",
@@ -1586,7 +1563,8 @@ fn json_fail_fast() {
   },
   {
     "elapsed_s": "[..]",
-    "event": "run_start"
+    "event": "run_start",
+    "seed": "{...}"
   },
   {
     "elapsed_s": "[..]",
@@ -0,0 +1,134 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use std::process::ExitCode;
+
+use libtest2::RunError;
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn exit_success(_context: &TestContext) -> ExitCode {
+    ExitCode::SUCCESS
+}
+
+#[libtest2::test]
+fn exit_failure(_context: &TestContext) -> ExitCode {
+    ExitCode::from(2)
+}
+
+#[libtest2::test]
+fn boxed_error(_context: &TestContext) -> Result<(), Box<dyn std::error::Error>> {
+    "one".parse::<u8>()?;
+    Ok(())
+}
+
+/// Like `anyhow::Error`, only `Debug`
+struct Report(&'static str);
+
+impl std::fmt::Debug for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "report: {}", self.0)
+    }
+}
+
+#[libtest2::test]
+fn debug_error(_context: &TestContext) -> Result<(), Report> {
+    Err(Report("broke"))
+}
+
+#[libtest2::test]
+fn string_error(_context: &TestContext) -> Result<(), String> {
+    Err("broke".to_owned())
+}
+
+#[libtest2::test]
+fn nested_ok(_context: &TestContext) -> Result<ExitCode, String> {
+    Ok(ExitCode::SUCCESS)
+}
+
+#[libtest2::test]
+fn nested_err(_context: &TestContext) -> Result<Result<(), String>, String> {
+    Ok(Err("inner".to_owned()))
+}
+
+#[libtest2::test]
+fn ignored(context: &TestContext) -> Result<(), RunError> {
+    context.ignore_for("not today")?;
+    Ok(())
+}
+
+#[libtest2::test]
+#[should_panic]
+fn should_panic_result(_context: &TestContext) -> Result<(), String> {
+    Ok(())
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 9 tests
+test boxed_error         ... FAILED
+test debug_error         ... FAILED
+test exit_failure        ... FAILED
+test exit_success        ... ok
+test ignored             ... ignored
+test nested_err          ... FAILED
+test nested_ok           ... ok
+test should_panic_result ... FAILED
+test string_error        ... FAILED
+
+failures:
+
+---- boxed_error ----
+ParseIntError { kind: InvalidDigit }
+
+---- debug_error ----
+report: broke
+
+---- exit_failure ----
+test returned a failing exit code: [..]
+
+---- nested_err ----
+"inner"
+
+---- should_panic_result ----
+test did not panic as expected
+
+---- string_error ----
+"broke"
+
+
+failures:
+    boxed_error
+    debug_error
+    exit_failure
+    nested_err
+    should_panic_result
+    string_error
+
+test result: FAILED. 2 passed; 6 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,184 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn seeded_fails(context: &TestContext) {
+    let value = context.rng().next_range(0..1000);
+    panic!("bad value {value}");
+}
+
+#[libtest2::test]
+fn seeded_ignored(context: &TestContext) -> libtest2::RunResult {
+    context.ignore_for("slow")?;
+    let value = context.rng().next_range(0..1000);
+    panic!("bad value {value}");
+}
+
+#[libtest2::test]
+fn seeded_passes(context: &TestContext) {
+    let _ = context.seed();
+}
+
+#[libtest2::test]
+fn unseeded_fails(_context: &TestContext) {
+    panic!("no randomness");
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn reproduce() {
+    test_cmd()
+        .args(["--seed", "42", "--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 4 tests
+test seeded_fails   ... FAILED
+test seeded_ignored ... ignored
+test seeded_passes  ... ok
+test unseeded_fails ... FAILED
+
+failures:
+
+---- seeded_fails ----
+test panicked: bad value 13
+
+---- seeded_fails seed ----
+seed: 15059800279754988902
+reproduce with: [..] --seed 42 --exact seeded_fails
+
+---- unseeded_fails ----
+test panicked: no randomness
+
+
+failures:
+    seeded_fails
+    unseeded_fails
+
+test result: FAILED. 1 passed; 2 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn reproduced() {
+    test_cmd()
+        .args(["--seed", "42", "--exact", "seeded_fails"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 1 test
+test seeded_fails ... FAILED
+
+failures:
+
+---- seeded_fails ----
+test panicked: bad value 13
+
+---- seeded_fails seed ----
+seed: 15059800279754988902
+reproduce with: [..] --seed 42 --exact seeded_fails
+
+
+failures:
+    seeded_fails
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn reproduce_ignored() {
+    test_cmd()
+        .args([
+            "--include-ignored",
+            "--seed",
+            "42",
+            "--exact",
+            "seeded_ignored",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 1 test
+test seeded_ignored ... FAILED
+
+failures:
+
+---- seeded_ignored ----
+test panicked: bad value 867
+
+---- seeded_ignored seed ----
+seed: 18333437089856490796
+reproduce with: [..] --include-ignored --seed 42 --exact seeded_ignored
+
+
+failures:
+    seeded_ignored
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn json() {
+    test_cmd()
+        .args([
+            "--seed",
+            "42",
+            "--test-threads",
+            "1",
+            "--format",
+            "json",
+            "-Zunstable-options",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"seeded_fails","elapsed_s":"[..]"}
+{"event":"discover_case","name":"seeded_ignored","elapsed_s":"[..]"}
+{"event":"discover_case","name":"seeded_passes","elapsed_s":"[..]"}
+{"event":"discover_case","name":"unseeded_fails","elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+{"event":"run_start","seed":42,"elapsed_s":"[..]"}
+{"event":"case_start","name":"seeded_fails","elapsed_s":"[..]"}
+{"event":"case_message","name":"seeded_fails","kind":"error","message":"test panicked: bad value 13","elapsed_s":"[..]"}
+{"event":"case_complete","name":"seeded_fails","seed":15059800279754988902,"elapsed_s":"[..]"}
+{"event":"case_start","name":"seeded_ignored","elapsed_s":"[..]"}
+{"event":"case_message","name":"seeded_ignored","kind":"ignored","message":"slow","elapsed_s":"[..]"}
+{"event":"case_complete","name":"seeded_ignored","elapsed_s":"[..]"}
+{"event":"case_start","name":"seeded_passes","elapsed_s":"[..]"}
+{"event":"case_complete","name":"seeded_passes","seed":3605708253028702620,"elapsed_s":"[..]"}
+{"event":"case_start","name":"unseeded_fails","elapsed_s":"[..]"}
+{"event":"case_message","name":"unseeded_fails","kind":"error","message":"test panicked: no randomness","elapsed_s":"[..]"}
+{"event":"case_complete","name":"unseeded_fails","elapsed_s":"[..]"}
+{"event":"run_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
@@ -0,0 +1,59 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Case as _;
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn located(_context: &TestContext) {
+    let _ = 1 + 1;
+}
+
+#[libtest2::test]
+#[ignore]
+async fn located_async() {}
+
+#[libtest2::test]
+fn report(_context: &TestContext) {
+    println!();
+    println!("{:?}", located.kind());
+    println!("{:?}", located.source());
+    println!("{:?}", located_async.source());
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn normal() {
+    test_cmd()
+        .args(["--test-threads", "1", "--exact", "report"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test report ... 
+IntegrationTest
+Some(Rust { source_file: "test.rs", start_line: 9, start_col: 1, end_line: 11, end_col: 1 })
+Some(Rust { source_file: "test.rs", start_line: 15, start_col: 7, end_line: 15, end_col: 27 })
+ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,82 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+fn untagged(_context: &TestContext) {}
+
+#[libtest2::test]
+#[tags("network")]
+fn fetch(_context: &TestContext) {}
+
+#[libtest2::test]
+#[tags("network")]
+#[ignore]
+#[tags("slow",)]
+fn download(_context: &TestContext) {}
+
+#[libtest2::test]
+#[tags("slow", "cpu")]
+#[case(1)]
+#[case(2)]
+fn compress(_context: &TestContext, _level: u32) {}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn include() {
+    test_cmd()
+        .args(["--include-tag", "slow", "--test-threads", "1"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 3 tests
+test compress::case_1 ... ok
+test compress::case_2 ... ok
+test download         ... ignored
+
+test result: ok. 2 passed; 0 failed; 1 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn json() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=json",
+            "--list",
+            "--exclude-tag",
+            "network+slow",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{"event":"discover_start","elapsed_s":"[..]"}
+{"event":"discover_case","name":"compress::case_1","tags":["slow","cpu"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"compress::case_2","tags":["slow","cpu"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"download","selected":false,"tags":["network","slow"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"fetch","tags":["network"],"elapsed_s":"[..]"}
+{"event":"discover_case","name":"untagged","elapsed_s":"[..]"}
+{"event":"discover_complete","elapsed_s":"[..]"}
+
+"#]]);
+}
@@ -49,6 +49,15 @@ pub fn new_file(name_prefix: &str, name_suffix: &str, content: &str) -> std::pat
     path
 }
 
+pub fn new_dir(name_prefix: &str) -> std::path::PathBuf {
+    static SUFFIX: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+    let suffix = SUFFIX.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    let path = tempdir().join(format!("{name_prefix}{suffix}"));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
 pub fn compile_test(package_root: &std::path::Path) -> std::path::PathBuf {
     let manifest_path = package_root.join("Cargo.toml");
     let target_name = package_root.file_name().unwrap().to_str().unwrap();
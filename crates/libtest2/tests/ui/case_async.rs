@@ -0,0 +1,8 @@
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+#[case(1)]
+async fn is_positive(_: &libtest2::TestContext, value: i32) {
+    assert!(0 < value);
+}
@@ -0,0 +1,20 @@
+use libtest2::Fixture;
+use libtest2::RunError;
+use libtest2::TestContext;
+
+#[libtest2::main]
+fn main() {}
+
+struct Database;
+
+impl Fixture for Database {
+    fn setup(_context: &TestContext) -> Result<Self, RunError> {
+        Ok(Database)
+    }
+}
+
+#[libtest2::test]
+#[case(1)]
+fn is_positive(_: &TestContext, _db: &Database, value: i32) {
+    assert!(0 < value);
+}
@@ -0,0 +1,12 @@
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+#[exclusive]
+#[exclusive]
+fn test_1(_: &libtest2::TestContext) {}
+
+#[libtest2::test]
+#[exclusive(|_| true)]
+#[serial]
+fn test_2(_: &libtest2::TestContext) {}
@@ -0,0 +1,6 @@
+#[libtest2::main]
+fn main() {}
+
+#[libtest2::test]
+#[executor(libtest2::executor::block_on)]
+fn not_async(_: &libtest2::TestContext) {}
@@ -0,0 +1,4 @@
+fn setup() {}
+
+#[libtest2::main(before_al = setup)]
+fn main() {}
@@ -6,7 +6,9 @@ fn bad_ok_variant(_: &libtest2::TestContext) -> Result<i32, std::io::Error> {
     Ok(0)
 }
 
+struct NotDebug;
+
 #[libtest2::test]
-fn bad_err_variant(_: &libtest2::TestContext) -> Result<(), String> {
+fn bad_err_variant(_: &libtest2::TestContext) -> Result<(), NotDebug> {
     Ok(())
 }